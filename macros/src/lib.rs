@@ -7,7 +7,7 @@ use convert_case::Casing;
 use proc_macro::{TokenStream};
 use proc_macro2::Span;
 use quote::quote;
-use syn::{punctuated::Punctuated, LitInt, Token, parse_macro_input, parse::Parse, Ident, token::Paren, parenthesized, Path, LitStr, ItemFn, Visibility, ItemStruct, Attribute};
+use syn::{punctuated::Punctuated, LitInt, Token, parse_macro_input, parse::Parse, Ident, token::Paren, parenthesized, bracketed, braced, Path, LitStr, ItemFn, Visibility, ItemStruct, Attribute, Expr};
 
 ///
 /// Auto-generates implementation of the `Die` trait for
@@ -319,6 +319,689 @@ pub fn skills(tokens : TokenStream) -> TokenStream {
     }.into()
 }
 
+///
+/// Defines new weapons, grouped by category and kind.
+///
+/// Damage is given as a `Rolls` expression, which is
+/// evaluated every time the weapon's damage is rolled.
+///
+/// ### Syntax
+/// `weapons!(Category Kind(Weapon(damage, DamageType, [Property, ...]), ...), ...)`
+///
+/// ***
+///
+/// ```ignore
+/// weapons!(
+///     Martial Melee(
+///         Longsword(D8(1), Slashing, [Versatile(D10(1))])
+///     )
+/// );
+/// ```
+///
+#[proc_macro]
+pub fn weapons(tokens : TokenStream) -> TokenStream {
+    struct PropertyDecl(Ident, Option<Punctuated<Expr, Token![,]>>);
+
+    impl Parse for PropertyDecl {
+        fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+            let ident = input.parse()?;
+            let args = match input.peek(Paren) {
+                true => {
+                    let stream;
+                    parenthesized!(stream in input);
+                    Some(stream.parse_terminated(Expr::parse, Token![,])?)
+                },
+                false => None,
+            };
+
+            Ok(Self(ident, args))
+        }
+    }
+
+    struct WeaponDecl(Vec<Attribute>, Ident, Expr, Ident, Punctuated<PropertyDecl, Token![,]>);
+
+    impl Parse for WeaponDecl {
+        fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+            let attrs = Attribute::parse_outer(input)?;
+            let ident = input.parse()?;
+
+            let stream;
+            parenthesized!(stream in input);
+            let damage = stream.parse()?;
+            stream.parse::<Token![,]>()?;
+            let damage_type = stream.parse()?;
+
+            let properties = match stream.parse::<Option<Token![,]>>()? {
+                Some(_) => {
+                    let list;
+                    bracketed!(list in stream);
+                    list.parse_terminated(PropertyDecl::parse, Token![,])?
+                },
+                None => Punctuated::new(),
+            };
+
+            Ok(Self(attrs, ident, damage, damage_type, properties))
+        }
+    }
+
+    struct WeaponGroup(Ident, Ident, Punctuated<WeaponDecl, Token![,]>);
+
+    impl Parse for WeaponGroup {
+        fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+            let category = input.parse()?;
+            let kind = input.parse()?;
+
+            let stream;
+            parenthesized!(stream in input);
+
+            Ok(Self(category, kind, stream.parse_terminated(WeaponDecl::parse, Token![,])?))
+        }
+    }
+
+    struct Weapons(Punctuated<WeaponGroup, Token![,]>);
+
+    impl Parse for Weapons {
+        fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+            Ok(Self(Punctuated::parse_terminated(input)?))
+        }
+    }
+
+    let groups = parse_macro_input!(tokens as Weapons);
+
+    let iter = groups.0.into_iter()
+        .flat_map(|WeaponGroup(category, kind, weapons)| {
+            weapons.into_iter()
+                .map(move |WeaponDecl(attrs, ident, damage, damage_type, properties)| {
+                    let id = format!("5E::WEAPON::{}", ident.to_string().to_uppercase());
+                    let doc_string = format!(
+                        "{} {} weapon: `{}` {} damage.",
+                        category,
+                        kind.to_string().to_lowercase(),
+                        quote!(#damage).to_string().replace(' ', ""),
+                        damage_type.to_string().to_lowercase()
+                    );
+
+                    let properties = properties.into_iter()
+                        .map(|PropertyDecl(prop, args)| match (prop.to_string().as_str(), args) {
+                            ("Versatile", Some(args)) => quote! { Property::Versatile(|| #args) },
+                            (_, Some(args)) => quote! { Property::#prop(#args) },
+                            (_, None) => quote! { Property::#prop },
+                        });
+
+                    quote! {
+                        #(#attrs)*
+                        #[doc = "***"]
+                        #[doc = #doc_string]
+                        #[doc = "***"]
+                        #[doc = "🤖 Autogenerated by [xander_macros::weapons]"]
+                        #[doc = ""]
+                        #[derive(Debug, Copy, Clone, Hash, PartialEq, Eq)]
+                        pub struct #ident;
+
+                        impl Identity for #ident {
+                            fn id(&self) -> &'static str {
+                                #id
+                            }
+
+                            fn __id() -> &'static str {
+                                #id
+                            }
+                        }
+
+                        impl Item for #ident {}
+
+                        impl WeaponGroup for #ident {}
+
+                        impl Weapon for #ident {
+                            fn category(&self) -> WeaponCategory {
+                                WeaponCategory::#category
+                            }
+
+                            fn kind(&self) -> WeaponKind {
+                                WeaponKind::#kind
+                            }
+
+                            fn damage(&self) -> Rolls {
+                                #damage
+                            }
+
+                            fn damage_type(&self) -> DamageType {
+                                DamageType::#damage_type
+                            }
+
+                            fn properties(&self) -> Vec<Property> {
+                                vec![#(#properties),*]
+                            }
+                        }
+                    }
+                })
+        });
+
+    quote! {
+        #(#iter)*
+    }.into()
+}
+
+///
+/// Defines new armor, grouped by category.
+///
+/// ### Syntax
+/// `armor!(Category(Armor(base_ac, [Strength(min), StealthDisadvantage]), ...), ...)`
+///
+/// ***
+///
+/// ```ignore
+/// armor!(
+///     Heavy(
+///         Plate(18, [Strength(15), StealthDisadvantage])
+///     )
+/// );
+/// ```
+///
+#[proc_macro]
+pub fn armor(tokens : TokenStream) -> TokenStream {
+    struct RequirementDecl(Ident, Option<LitInt>);
+
+    impl Parse for RequirementDecl {
+        fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+            let ident = input.parse()?;
+            let arg = match input.peek(Paren) {
+                true => {
+                    let stream;
+                    parenthesized!(stream in input);
+                    Some(stream.parse()?)
+                },
+                false => None,
+            };
+
+            Ok(Self(ident, arg))
+        }
+    }
+
+    struct ArmorDecl(Vec<Attribute>, Ident, LitInt, Punctuated<RequirementDecl, Token![,]>);
+
+    impl Parse for ArmorDecl {
+        fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+            let attrs = Attribute::parse_outer(input)?;
+            let ident = input.parse()?;
+
+            let stream;
+            parenthesized!(stream in input);
+            let base = stream.parse()?;
+
+            let requirements = match stream.parse::<Option<Token![,]>>()? {
+                Some(_) => {
+                    let list;
+                    bracketed!(list in stream);
+                    list.parse_terminated(RequirementDecl::parse, Token![,])?
+                },
+                None => Punctuated::new(),
+            };
+
+            Ok(Self(attrs, ident, base, requirements))
+        }
+    }
+
+    struct ArmorGroup(Ident, Punctuated<ArmorDecl, Token![,]>);
+
+    impl Parse for ArmorGroup {
+        fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+            let category = input.parse()?;
+
+            let stream;
+            parenthesized!(stream in input);
+
+            Ok(Self(category, stream.parse_terminated(ArmorDecl::parse, Token![,])?))
+        }
+    }
+
+    struct ArmorList(Punctuated<ArmorGroup, Token![,]>);
+
+    impl Parse for ArmorList {
+        fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+            Ok(Self(Punctuated::parse_terminated(input)?))
+        }
+    }
+
+    let groups = parse_macro_input!(tokens as ArmorList);
+
+    let mut errors = vec![];
+
+    let iter = groups.0.into_iter()
+        .flat_map(|ArmorGroup(category, armor)| {
+            armor.into_iter()
+                .map(move |decl| (category.clone(), decl))
+        })
+        .map(|(category, ArmorDecl(attrs, ident, base, requirements))| {
+            let id = format!("5E::ARMOR::{}", ident.to_string().to_uppercase());
+            let doc_string = format!("{} armor: base AC {}.", category, base.base10_digits());
+
+            let mut strength = quote! { None };
+            let mut stealth = quote! { false };
+
+            for RequirementDecl(req, arg) in requirements {
+                match (req.to_string().as_str(), arg) {
+                    ("Strength", Some(min)) => strength = quote! { Some(#min) },
+                    ("StealthDisadvantage", None) => stealth = quote! { true },
+                    _ => errors.push(
+                        syn::Error::new(req.span(), "expected `Strength(min)` or `StealthDisadvantage`")
+                            .to_compile_error()
+                    ),
+                }
+            }
+
+            quote! {
+                #(#attrs)*
+                #[doc = "***"]
+                #[doc = #doc_string]
+                #[doc = "***"]
+                #[doc = "🤖 Autogenerated by [xander_macros::armor]"]
+                #[doc = ""]
+                #[derive(Debug, Copy, Clone, Hash, PartialEq, Eq)]
+                pub struct #ident;
+
+                impl Identity for #ident {
+                    fn id(&self) -> &'static str {
+                        #id
+                    }
+
+                    fn __id() -> &'static str {
+                        #id
+                    }
+                }
+
+                impl Item for #ident {}
+
+                impl ArmorGroup for #ident {}
+
+                impl Armor for #ident {
+                    fn category(&self) -> ArmorCategory {
+                        ArmorCategory::#category
+                    }
+
+                    fn base(&self) -> i32 {
+                        #base
+                    }
+
+                    fn strength(&self) -> Option<usize> {
+                        #strength
+                    }
+
+                    fn stealth_disadvantage(&self) -> bool {
+                        #stealth
+                    }
+                }
+            }
+        })
+        .collect::<Vec<_>>();
+
+    quote! {
+        #(#errors)*
+        #(#iter)*
+    }.into()
+}
+
+///
+/// A flat list of grouped declarations:
+/// `Group(Entry1, Entry2, ...), ...`
+///
+/// Shared by the catalogue-style macros ([tools], [languages]).
+///
+struct Catalogue(Punctuated<CatalogueGroup, Token![,]>);
+
+struct CatalogueGroup(Ident, Punctuated<CatalogueEntry, Token![,]>);
+
+struct CatalogueEntry(Vec<Attribute>, Ident);
+
+impl Parse for CatalogueEntry {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        Ok(Self(Attribute::parse_outer(input)?, input.parse()?))
+    }
+}
+
+impl Parse for CatalogueGroup {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let group = input.parse()?;
+
+        let stream;
+        parenthesized!(stream in input);
+
+        Ok(Self(group, stream.parse_terminated(CatalogueEntry::parse, Token![,])?))
+    }
+}
+
+impl Parse for Catalogue {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        Ok(Self(Punctuated::parse_terminated(input)?))
+    }
+}
+
+impl Catalogue {
+    fn into_iter(self) -> impl Iterator<Item = (Ident, Vec<Attribute>, Ident)> {
+        self.0.into_iter()
+            .flat_map(|CatalogueGroup(group, entries)| {
+                entries.into_iter()
+                    .map(move |CatalogueEntry(attrs, ident)| (group.clone(), attrs, ident))
+            })
+    }
+}
+
+///
+/// Defines new tools, grouped by category.
+///
+/// ### Syntax
+/// `tools!(Category(Tool1, Tool2, ...), ...)`
+///
+#[proc_macro]
+pub fn tools(tokens : TokenStream) -> TokenStream {
+    let tools = parse_macro_input!(tokens as Catalogue);
+
+    let iter = tools.into_iter()
+        .map(|(category, attrs, ident)| {
+            let id = format!("5E::TOOL::{}", ident.to_string().to_uppercase());
+            let doc_string = format!("Tool category: {}", category);
+            quote! {
+                #(#attrs)*
+                #[doc = "***"]
+                #[doc = #doc_string]
+                #[doc = "***"]
+                #[doc = "🤖 Autogenerated by [xander_macros::tools]"]
+                #[doc = ""]
+                #[derive(Debug, Copy, Clone, Hash, PartialEq, Eq)]
+                pub struct #ident;
+
+                impl Identity for #ident {
+                    fn id(&self) -> &'static str {
+                        #id
+                    }
+
+                    fn __id() -> &'static str {
+                        #id
+                    }
+                }
+
+                impl Item for #ident {}
+
+                impl Tool for #ident {
+                    fn category(&self) -> ToolCategory {
+                        ToolCategory::#category
+                    }
+                }
+            }
+        });
+
+    quote! {
+        #(#iter)*
+    }.into()
+}
+
+///
+/// Defines new languages, grouped by
+/// how common they are.
+///
+/// ### Syntax
+/// `languages!(Standard(Language1, ...), Exotic(...))`
+///
+#[proc_macro]
+pub fn languages(tokens : TokenStream) -> TokenStream {
+    let languages = parse_macro_input!(tokens as Catalogue);
+
+    let iter = languages.into_iter()
+        .map(|(kind, attrs, ident)| {
+            let id = format!("5E::LANGUAGE::{}", ident.to_string().to_uppercase());
+            let doc_string = format!("{} language.", kind);
+            quote! {
+                #(#attrs)*
+                #[doc = "***"]
+                #[doc = #doc_string]
+                #[doc = "***"]
+                #[doc = "🤖 Autogenerated by [xander_macros::languages]"]
+                #[doc = ""]
+                #[derive(Debug, Copy, Clone, Hash, PartialEq, Eq)]
+                pub struct #ident;
+
+                impl Identity for #ident {
+                    fn id(&self) -> &'static str {
+                        #id
+                    }
+
+                    fn __id() -> &'static str {
+                        #id
+                    }
+                }
+
+                impl Language for #ident {
+                    fn kind(&self) -> LanguageKind {
+                        LanguageKind::#kind
+                    }
+                }
+            }
+        });
+
+    quote! {
+        #(#iter)*
+    }.into()
+}
+
+///
+/// Defines new spells.
+///
+/// Every field but `damage`, `upcast`, `cantrip`,
+/// `concentration` and `ritual` is required.
+/// Expressions may use the variants of the matching enum
+/// unqualified (e.g. `range: Feet(150)`).
+///
+/// ### Syntax
+/// `spells!(Spell { level: N, school: School, casting_time: ..., range: ..., components: [...], duration: ..., effect: ..., damage: (rolls, DamageType), upcast: rolls, concentration, ritual }, ...)`
+///
+/// ***
+///
+/// ```ignore
+/// spells!(
+///     Fireball {
+///         level: 3,
+///         school: Evocation,
+///         casting_time: Action,
+///         range: Feet(150),
+///         components: [Verbal, Somatic, Material("a tiny ball of bat guano and sulfur")],
+///         duration: Instantaneous,
+///         effect: Save(&Dexterity, Half),
+///         damage: (D6(8), Fire),
+///         upcast: D6(1),
+///     }
+/// );
+/// ```
+///
+#[proc_macro]
+pub fn spells(tokens : TokenStream) -> TokenStream {
+    struct FieldDecl(Ident, Option<Expr>);
+
+    impl Parse for FieldDecl {
+        fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+            let key = input.parse()?;
+            let value = match input.parse::<Option<Token![:]>>()? {
+                Some(_) => Some(input.parse()?),
+                None => None,
+            };
+
+            Ok(Self(key, value))
+        }
+    }
+
+    struct SpellDecl(Vec<Attribute>, Ident, Punctuated<FieldDecl, Token![,]>);
+
+    impl Parse for SpellDecl {
+        fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+            let attrs = Attribute::parse_outer(input)?;
+            let ident = input.parse()?;
+
+            let stream;
+            braced!(stream in input);
+
+            Ok(Self(attrs, ident, stream.parse_terminated(FieldDecl::parse, Token![,])?))
+        }
+    }
+
+    struct Spells(Punctuated<SpellDecl, Token![,]>);
+
+    impl Parse for Spells {
+        fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+            Ok(Self(Punctuated::parse_terminated(input)?))
+        }
+    }
+
+    let spells = parse_macro_input!(tokens as Spells);
+
+    let iter = spells.0.into_iter()
+        .map(|SpellDecl(attrs, ident, fields)| {
+            let mut fields = fields.into_iter()
+                .map(|FieldDecl(key, value)| (key.to_string(), (key, value)))
+                .collect::<std::collections::HashMap<_, _>>();
+
+            let mut required = |name : &str| match fields.remove(name) {
+                Some((_, Some(value))) => Ok(value),
+                Some((key, None)) => Err(syn::Error::new(key.span(), format!("expected `{name}: ...`"))),
+                None => Err(syn::Error::new(ident.span(), format!("missing field `{name}`"))),
+            };
+
+            let (level, school, casting_time, range, components, duration, effect) = match (
+                required("level"),
+                required("school"),
+                required("casting_time"),
+                required("range"),
+                required("components"),
+                required("duration"),
+                required("effect"),
+            ) {
+                (Ok(a), Ok(b), Ok(c), Ok(d), Ok(e), Ok(f), Ok(g)) => (a, b, c, d, e, f, g),
+                (a, b, c, d, e, f, g) => {
+                    let errors = [a.err(), b.err(), c.err(), d.err(), e.err(), f.err(), g.err()]
+                        .into_iter()
+                        .flatten()
+                        .map(|e| e.to_compile_error());
+                    return quote! { #(#errors)* };
+                },
+            };
+
+            let concentration = fields.remove("concentration").is_some();
+            let ritual = fields.remove("ritual").is_some();
+
+            let (damage, damage_type) = match fields.remove("damage").and_then(|(_, v)| v) {
+                Some(Expr::Tuple(tuple)) if tuple.elems.len() == 2 => {
+                    let (damage, damage_type) = (&tuple.elems[0], &tuple.elems[1]);
+                    (quote! { Some(#damage) }, quote! { Some(DamageType::#damage_type) })
+                },
+                Some(other) => {
+                    return syn::Error::new_spanned(other, "expected `(rolls, DamageType)`")
+                        .to_compile_error();
+                },
+                None => (quote! { None }, quote! { None }),
+            };
+
+            let scaling = match (
+                fields.remove("upcast").and_then(|(_, v)| v),
+                fields.remove("cantrip").and_then(|(_, v)| v),
+            ) {
+                (Some(extra), _) => quote! { Scaling::PerSlotLevel(|| #extra) },
+                (None, Some(extra)) => quote! { Scaling::PerTier(|| #extra) },
+                (None, None) => quote! { Scaling::None },
+            };
+
+            if let Some((key, _)) = fields.into_values().next() {
+                return syn::Error::new(key.span(), format!("unknown field `{key}`"))
+                    .to_compile_error();
+            }
+
+            let id = format!("5E::SPELL::{}", ident.to_string().to_case(convert_case::Case::UpperSnake));
+            let doc_string = match quote!(#level).to_string().as_str() {
+                "0" => format!("{} cantrip.", quote!(#school)),
+                level => format!("Level {} {}.", level, quote!(#school).to_string().to_lowercase()),
+            };
+
+            quote! {
+                #(#attrs)*
+                #[doc = "***"]
+                #[doc = #doc_string]
+                #[doc = "***"]
+                #[doc = "🤖 Autogenerated by [xander_macros::spells]"]
+                #[doc = ""]
+                #[derive(Debug, Copy, Clone, Hash, PartialEq, Eq)]
+                pub struct #ident;
+
+                impl Identity for #ident {
+                    fn id(&self) -> &'static str {
+                        #id
+                    }
+
+                    fn __id() -> &'static str {
+                        #id
+                    }
+                }
+
+                impl Spell for #ident {
+                    fn level(&self) -> usize {
+                        #level
+                    }
+
+                    fn school(&self) -> School {
+                        School::#school
+                    }
+
+                    fn casting_time(&self) -> CastingTime {
+                        use CastingTime::*;
+                        #casting_time
+                    }
+
+                    fn range(&self) -> Range {
+                        use Range::*;
+                        #range
+                    }
+
+                    fn components(&self) -> Vec<Component> {
+                        use Component::*;
+                        vec!#components
+                    }
+
+                    fn duration(&self) -> Duration {
+                        use Duration::*;
+                        #duration
+                    }
+
+                    fn concentration(&self) -> bool {
+                        #concentration
+                    }
+
+                    fn ritual(&self) -> bool {
+                        #ritual
+                    }
+
+                    fn effect(&self) -> Effect {
+                        use Effect::*;
+                        use OnSave::*;
+                        use AttackKind::*;
+                        #effect
+                    }
+
+                    fn damage_type(&self) -> Option<DamageType> {
+                        #damage_type
+                    }
+
+                    fn base_damage(&self) -> Option<Rolls> {
+                        #damage
+                    }
+
+                    fn scaling(&self) -> Scaling {
+                        #scaling
+                    }
+                }
+            }
+        });
+
+    quote! {
+        #(#iter)*
+    }.into()
+}
+
 #[proc_macro_attribute]
 #[allow(non_snake_case)]
 pub fn Proficiency(attr : TokenStream, body : TokenStream) -> TokenStream {
@@ -339,9 +1022,17 @@ pub fn Proficiency(attr : TokenStream, body : TokenStream) -> TokenStream {
     let ident = &body.ident;
     let traits = body.fields.iter().map(|f| &f.ty).collect::<Vec<_>>();
 
+    let category_ident = Ident::new(&ident.to_string().to_case(convert_case::Case::UpperSnake), ident.span());
+    let category_doc = format!("Category id of [{}], for category-wide settings (e.g. fallbacks).", ident);
+
     quote! {
+        #[doc = #category_doc]
+        #[doc = "***"]
+        #[doc = "🤖 Autogenerated by [xander_macros::Proficiency]"]
+        pub const #category_ident: &str = #id;
+
         #(#original_attrs)*
-        #[derive(std::fmt::Debug, core::hash::Hash, core::cmp::PartialEq, core::cmp::Eq)]
+        #[derive(std::fmt::Debug, core::clone::Clone, core::hash::Hash, core::cmp::PartialEq, core::cmp::Eq)]
         pub struct #ident<T: #(#traits)+* + std::fmt::Debug + core::hash::Hash + core::cmp::Eq>(pub T);
 
         impl<T: #(#traits)+* + std::fmt::Debug + core::hash::Hash + core::cmp::Eq> Proficiency<T> for #ident<T> {
@@ -394,6 +1085,9 @@ pub fn identify(tokens : TokenStream) -> TokenStream {
 ///
 /// Simplifies the process of creating a new proficiency
 /// type.
+///
+/// An optional `rank = N` sets its precedence
+/// (see `ProficiencyType::rank`).
 /// ***
 /// ```
 /// use xander_macros::ProficiencyType;
@@ -401,6 +1095,7 @@ pub fn identify(tokens : TokenStream) -> TokenStream {
 ///     Identity,
 ///     creature::{
 ///         Creature,
+///         context::CheckContext,
 ///         proficiency::{Proficiency, ProficiencyType, ProficiencyTyped}
 ///     }
 /// };
@@ -409,8 +1104,8 @@ pub fn identify(tokens : TokenStream) -> TokenStream {
 /// /// The cool new 'triple' proficiency.
 /// /// It's even better than Expertise!
 /// ///
-/// #[ProficiencyType("HOMEBREW::PROFICIENCY_TYPE::TRIPLE")]
-/// pub fn Triple(&self, _ : &Creature, prof_bonus : i32) -> i32 {
+/// #[ProficiencyType("HOMEBREW::PROFICIENCY_TYPE::TRIPLE", rank = 4)]
+/// pub fn Triple(&self, _ : &Creature, _ : &CheckContext, prof_bonus : i32) -> i32 {
 ///     prof_bonus * 3
 /// }
 /// ```
@@ -419,14 +1114,32 @@ pub fn identify(tokens : TokenStream) -> TokenStream {
 #[allow(non_snake_case)]
 pub fn ProficiencyType(attrs : TokenStream, body : TokenStream) -> TokenStream {
     use convert_case::Case::Snake;
-    struct AttrInput(LitStr);
+    struct AttrInput(LitStr, Option<LitInt>);
     impl Parse for AttrInput {
         fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
-            Ok(Self(input.parse()?))
+            let id = input.parse()?;
+            let rank = match input.parse::<Option<Token![,]>>()? {
+                Some(_) => {
+                    let key = input.parse::<Ident>()?;
+                    if key != "rank" {
+                        return Err(syn::Error::new(key.span(), "expected `rank = ...`"));
+                    }
+                    input.parse::<Token![=]>()?;
+                    Some(input.parse()?)
+                },
+                None => None,
+            };
+
+            Ok(Self(id, rank))
         }
     }
 
-    let AttrInput(id) = parse_macro_input!(attrs as AttrInput);
+    let AttrInput(id, rank) = parse_macro_input!(attrs as AttrInput);
+    let rank = rank.map(|rank| quote! {
+        fn rank(&self) -> u8 {
+            #rank
+        }
+    });
 
     let mut body = parse_macro_input!(body as ItemFn);
     let ident = body.sig.ident.clone();
@@ -450,6 +1163,8 @@ pub fn ProficiencyType(attrs : TokenStream, body : TokenStream) -> TokenStream {
 
         impl ProficiencyType for #ident {
             #body
+
+            #rank
         }
 
         pub trait #util_trait_ident<I, P>
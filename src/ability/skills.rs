@@ -2,9 +2,9 @@ use core::hash::Hash;
 
 use xander_macros::skills;
 
-use crate::identity::Identity;
+use crate::{creature::proficiency::Offer, identity::Identity};
 
-use super::{Ability, Charisma, Check, Dexterity, Intelligence, Strength, Wisdom};
+use super::{Ability, Charisma, Check, Checks, Dexterity, Intelligence, Strength, Wisdom};
 
 pub trait Skill: Check + Identity {
     fn base(&self) -> Box<dyn Ability>;
@@ -94,3 +94,30 @@ skills!(
         Persuasion
     )
 );
+
+///
+/// Proficiency in every skill, on offer
+/// (e.g. for "any skill of your choice").
+///
+pub fn offers() -> Vec<Offer> {
+    vec![
+        Offer::new(Checks(Athletics)),
+        Offer::new(Checks(Acrobatics)),
+        Offer::new(Checks(SleightOfHand)),
+        Offer::new(Checks(Stealth)),
+        Offer::new(Checks(Arcana)),
+        Offer::new(Checks(History)),
+        Offer::new(Checks(Investigation)),
+        Offer::new(Checks(Nature)),
+        Offer::new(Checks(Religion)),
+        Offer::new(Checks(AnimalHandling)),
+        Offer::new(Checks(Insight)),
+        Offer::new(Checks(Medicine)),
+        Offer::new(Checks(Perception)),
+        Offer::new(Checks(Survival)),
+        Offer::new(Checks(Deception)),
+        Offer::new(Checks(Intimidation)),
+        Offer::new(Checks(Performance)),
+        Offer::new(Checks(Persuasion)),
+    ]
+}
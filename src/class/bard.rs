@@ -0,0 +1,84 @@
+use xander_macros::identify;
+
+use crate::{
+    ability::{skills, Charisma, Dexterity, Saves},
+    creature::proficiency::Offer,
+    feature::{Feature, JackOfAllTrades},
+    identity::Identity,
+    item::{
+        armor::ArmorCategory,
+        weapon::{HandCrossbow, Longsword, Rapier, Shortsword, WeaponCategory},
+        Armor, Weapons,
+    },
+    spell::slots::{Caster, Progression},
+};
+
+use super::{Class, Prerequisite};
+
+///
+/// Performers whose music weaves magic.
+///
+/// Musical instruments and Expertise are
+/// choices, and are left to the caller.
+///
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
+pub struct Bard;
+
+identify!(Bard, "5E::CLASS::BARD");
+
+impl Class for Bard {
+    fn hit_die(&self) -> usize {
+        8
+    }
+
+    fn saves(&self) -> Vec<Offer> {
+        vec![Offer::new(Saves(Dexterity)), Offer::new(Saves(Charisma))]
+    }
+
+    fn skills(&self) -> (usize, Vec<Offer>) {
+        (3, skills::offers())
+    }
+
+    fn proficiencies(&self) -> Vec<Offer> {
+        vec![
+            Offer::new(Armor(ArmorCategory::Light)),
+            Offer::new(Weapons(WeaponCategory::Simple)),
+            Offer::new(Weapons(HandCrossbow)),
+            Offer::new(Weapons(Longsword)),
+            Offer::new(Weapons(Rapier)),
+            Offer::new(Weapons(Shortsword)),
+        ]
+    }
+
+    fn prerequisites(&self) -> Vec<Prerequisite> {
+        vec![vec![(Charisma.id(), 13)]]
+    }
+
+    fn multiclass_proficiencies(&self) -> Vec<Offer> {
+        vec![Offer::new(Armor(ArmorCategory::Light))]
+    }
+
+    fn multiclass_skills(&self) -> usize {
+        1
+    }
+
+    fn features(&self, level: usize) -> Vec<Box<dyn Feature>> {
+        match level {
+            2 => vec![Box::new(JackOfAllTrades)],
+            _ => vec![],
+        }
+    }
+
+    fn spells_learned(&self, level: usize) -> usize {
+        match level {
+            1 => 4,
+            10 | 14 | 18 => 2,
+            12 | 16 | 19 | 20 => 0,
+            _ => 1,
+        }
+    }
+
+    fn caster(&self, level: usize) -> Option<Caster> {
+        Some(Caster::new(self.id(), Charisma, Progression::Full, level))
+    }
+}
@@ -0,0 +1,109 @@
+use xander_macros::identify;
+
+use crate::{
+    ability::{
+        skills::{
+            Acrobatics, AnimalHandling, Athletics, History, Insight, Intimidation, Perception,
+            Survival,
+        },
+        Checks, Constitution, Dexterity, Saves, Strength,
+    },
+    creature::proficiency::Offer,
+    feature::{ActionSurge, Feature, RemarkableAthlete, SecondWind},
+    identity::Identity,
+    item::{armor::ArmorCategory, weapon::WeaponCategory, Armor, Weapons},
+};
+
+use super::{Class, Prerequisite, Subclass};
+
+///
+/// Masters of martial combat.
+///
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
+pub struct Fighter;
+
+identify!(Fighter, "5E::CLASS::FIGHTER");
+
+impl Class for Fighter {
+    fn hit_die(&self) -> usize {
+        10
+    }
+
+    fn saves(&self) -> Vec<Offer> {
+        vec![Offer::new(Saves(Strength)), Offer::new(Saves(Constitution))]
+    }
+
+    fn skills(&self) -> (usize, Vec<Offer>) {
+        (
+            2,
+            vec![
+                Offer::new(Checks(Acrobatics)),
+                Offer::new(Checks(AnimalHandling)),
+                Offer::new(Checks(Athletics)),
+                Offer::new(Checks(History)),
+                Offer::new(Checks(Insight)),
+                Offer::new(Checks(Intimidation)),
+                Offer::new(Checks(Perception)),
+                Offer::new(Checks(Survival)),
+            ],
+        )
+    }
+
+    fn proficiencies(&self) -> Vec<Offer> {
+        vec![
+            Offer::new(Armor(ArmorCategory::Light)),
+            Offer::new(Armor(ArmorCategory::Medium)),
+            Offer::new(Armor(ArmorCategory::Heavy)),
+            Offer::new(Armor(ArmorCategory::Shield)),
+            Offer::new(Weapons(WeaponCategory::Simple)),
+            Offer::new(Weapons(WeaponCategory::Martial)),
+        ]
+    }
+
+    fn prerequisites(&self) -> Vec<Prerequisite> {
+        vec![vec![(Strength.id(), 13), (Dexterity.id(), 13)]]
+    }
+
+    fn multiclass_proficiencies(&self) -> Vec<Offer> {
+        vec![
+            Offer::new(Armor(ArmorCategory::Light)),
+            Offer::new(Armor(ArmorCategory::Medium)),
+            Offer::new(Armor(ArmorCategory::Shield)),
+            Offer::new(Weapons(WeaponCategory::Simple)),
+            Offer::new(Weapons(WeaponCategory::Martial)),
+        ]
+    }
+
+    fn improvements(&self) -> Vec<usize> {
+        vec![4, 6, 8, 12, 14, 16, 19]
+    }
+
+    fn features(&self, level: usize) -> Vec<Box<dyn Feature>> {
+        match level {
+            1 => vec![Box::new(SecondWind)],
+            2 => vec![Box::new(ActionSurge)],
+            _ => vec![],
+        }
+    }
+}
+
+///
+/// Martial Archetype focused on raw physical power.
+///
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
+pub struct Champion;
+
+identify!(Champion, "5E::SUBCLASS::CHAMPION");
+
+impl Subclass for Champion {
+    fn class(&self) -> &'static str {
+        Fighter.id()
+    }
+
+    fn features(&self, level: usize) -> Vec<Box<dyn Feature>> {
+        match level {
+            7 => vec![Box::new(RemarkableAthlete)],
+            _ => vec![],
+        }
+    }
+}
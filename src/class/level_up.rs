@@ -0,0 +1,295 @@
+use crate::{creature::Creature, feature::Feature, spell::Spell};
+
+use super::{Class, ClassError, Subclass};
+
+///
+/// How hit points are gained past the first character level
+/// (which always gets the most the hit die allows).
+///
+#[derive(Debug, Default, Clone, Copy, Hash, PartialEq, Eq)]
+pub enum HitPoints {
+    ///
+    /// Half the hit die, plus one.
+    ///
+    #[default]
+    Average,
+    ///
+    /// Roll the hit die.
+    ///
+    Roll,
+}
+
+///
+/// An Ability Score Improvement, or a feat in its place.
+///
+#[derive(Debug)]
+pub enum Improvement {
+    ///
+    /// +2 to one ability score, or +1 to two, by ability id.
+    ///
+    Scores(Vec<(&'static str, usize)>),
+    Feat(Box<dyn Feature>),
+}
+
+impl Improvement {
+    pub fn feat(feat: impl Feature + 'static) -> Self {
+        Self::Feat(Box::new(feat))
+    }
+}
+
+///
+/// Something to decide on when taking a level.
+///
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Choice {
+    ///
+    /// Average or rolled hit points, on a die with this many sides.
+    ///
+    HitPoints(usize),
+    ///
+    /// Skills to pick, by id.
+    ///
+    Skills {
+        count: usize,
+        from: Vec<&'static str>,
+    },
+    Improvement,
+    Subclass,
+    ///
+    /// New spells, up to a spell level.
+    ///
+    Spells {
+        count: usize,
+        level: usize,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LevelUpError {
+    Class(ClassError),
+    ///
+    /// A choice that had to be made wasn't.
+    ///
+    Missing(Choice),
+    ///
+    /// A choice was made that isn't on offer at this level.
+    ///
+    Unexpected(Choice),
+    ///
+    /// Not +2 to one score or +1 to two,
+    /// or a score would go past 20.
+    ///
+    InvalidScores,
+    ///
+    /// Feat already taken.
+    ///
+    FeatTaken(&'static str),
+    ///
+    /// Wrong number of spells picked.
+    ///
+    SpellCount {
+        expected: usize,
+        got: usize,
+    },
+    ///
+    /// Spell too high a level, already known, or picked twice.
+    ///
+    InvalidSpell(&'static str),
+}
+
+impl From<ClassError> for LevelUpError {
+    fn from(err: ClassError) -> Self {
+        Self::Class(err)
+    }
+}
+
+///
+/// Every choice for a single level, made up front
+/// and applied all at once by [Creature::level_up].
+///
+/// ***
+/// ```
+/// use xander::{
+///     ability::{skills::{Athletics, Perception}, Strength},
+///     class::{Choice, Fighter, LevelUp, LevelUpError},
+///     creature::Creature,
+///     identity::Identity,
+/// };
+///
+/// let mut ent = Creature::default();
+/// ent.stats().insert(Strength.id(), 15);
+///
+/// let pending = LevelUp::pending(&ent, &Fighter);
+/// assert!(matches!(pending[0], Choice::Skills { count: 2, .. }));
+///
+/// assert!(matches!(
+///     ent.level_up(LevelUp::new(&Fighter)),
+///     Err(LevelUpError::Missing(Choice::Skills { .. }))
+/// ));
+/// ent.level_up(LevelUp::new(&Fighter).skills(&[Athletics.id(), Perception.id()])).unwrap();
+/// assert_eq!(ent.level(), 1);
+///
+/// ent.undo_level_up();
+/// assert_eq!(ent.level(), 0);
+/// ```
+///
+#[derive(Debug)]
+pub struct LevelUp {
+    pub(crate) class: &'static dyn Class,
+    pub(crate) hit_points: HitPoints,
+    pub(crate) skills: Vec<&'static str>,
+    pub(crate) improvement: Option<Improvement>,
+    pub(crate) subclass: Option<&'static dyn Subclass>,
+    pub(crate) spells: Vec<&'static dyn Spell>,
+}
+
+impl LevelUp {
+    pub fn new(class: &'static dyn Class) -> Self {
+        Self {
+            class,
+            hit_points: HitPoints::default(),
+            skills: vec![],
+            improvement: None,
+            subclass: None,
+            spells: vec![],
+        }
+    }
+
+    ///
+    /// Choices to be made for `ent` to take a level in `class`.
+    ///
+    pub fn pending(ent: &Creature, class: &'static dyn Class) -> Vec<Choice> {
+        let existing = ent.class_level(class.id());
+        let level = existing + 1;
+        let mut choices = vec![];
+
+        if ent.level() > 0 {
+            choices.push(Choice::HitPoints(class.hit_die()));
+        }
+
+        let (count, offers) = class.skills();
+        let count = match (ent.level(), existing) {
+            (0, _) => count,
+            (_, 0) => class.multiclass_skills(),
+            _ => 0,
+        };
+        if count > 0 {
+            let from = offers.iter().map(|o| o.id()).collect();
+            choices.push(Choice::Skills { count, from });
+        }
+
+        if class.improvements().contains(&level) {
+            choices.push(Choice::Improvement);
+        }
+
+        if class.subclass_level() == level {
+            choices.push(Choice::Subclass);
+        }
+
+        let count = class.spells_learned(level);
+        if count > 0 {
+            let level = class.caster(level).map_or(0, |c| c.spell_level());
+            choices.push(Choice::Spells { count, level });
+        }
+
+        choices
+    }
+
+    pub fn hit_points(mut self, hit_points: HitPoints) -> Self {
+        self.hit_points = hit_points;
+        self
+    }
+
+    pub fn skills(mut self, skills: &[&'static str]) -> Self {
+        self.skills = skills.to_vec();
+        self
+    }
+
+    pub fn improvement(mut self, improvement: Improvement) -> Self {
+        self.improvement = Some(improvement);
+        self
+    }
+
+    pub fn subclass(mut self, subclass: &'static dyn Subclass) -> Self {
+        self.subclass = Some(subclass);
+        self
+    }
+
+    pub fn spells(mut self, spells: &[&'static dyn Spell]) -> Self {
+        self.spells = spells.to_vec();
+        self
+    }
+
+    pub fn class(&self) -> &'static dyn Class {
+        self.class
+    }
+
+    ///
+    /// Check every choice against what `ent` is offered,
+    /// short of what [Creature::add_level] checks itself.
+    ///
+    pub fn validate(&self, ent: &Creature) -> Result<(), LevelUpError> {
+        let pending = Self::pending(ent, self.class);
+        let find = |f: fn(&Choice) -> bool| pending.iter().find(|c| f(c)).cloned();
+
+        match (find(|c| *c == Choice::Improvement), &self.improvement) {
+            (Some(choice), None) => return Err(LevelUpError::Missing(choice)),
+            (None, Some(_)) => return Err(LevelUpError::Unexpected(Choice::Improvement)),
+            (Some(_), Some(Improvement::Scores(scores))) => {
+                let valid = match scores.as_slice() {
+                    [(_, 2)] => true,
+                    [(a, 1), (b, 1)] => a != b,
+                    _ => false,
+                };
+                let capped = scores.iter().all(|(ability, increase)| {
+                    ent.score_id(ability).is_some_and(|s| s + increase <= 20)
+                });
+
+                if !(valid && capped) {
+                    return Err(LevelUpError::InvalidScores);
+                }
+            }
+            (Some(_), Some(Improvement::Feat(feat))) => {
+                if ent.features().any(|f| f.id() == feat.id()) {
+                    return Err(LevelUpError::FeatTaken(feat.id()));
+                }
+            }
+            (None, None) => {}
+        }
+
+        match (find(|c| *c == Choice::Subclass), self.subclass) {
+            (Some(choice), None) => return Err(LevelUpError::Missing(choice)),
+            (None, Some(_)) => return Err(LevelUpError::Unexpected(Choice::Subclass)),
+            (Some(_), Some(subclass)) if subclass.class() != self.class.id() => {
+                return Err(ClassError::MissingClass(subclass.class()).into())
+            }
+            _ => {}
+        }
+
+        let (count, level) = match find(|c| matches!(c, Choice::Spells { .. })) {
+            Some(Choice::Spells { count, level }) => (count, level),
+            _ => (0, 0),
+        };
+        if self.spells.len() != count {
+            return Err(LevelUpError::SpellCount {
+                expected: count,
+                got: self.spells.len(),
+            });
+        }
+
+        for (i, spell) in self.spells.iter().enumerate() {
+            let known = ent.spells().iter().any(|s| s.id() == spell.id())
+                || self.spells[..i].iter().any(|s| s.id() == spell.id());
+
+            if known || !(1..=level).contains(&spell.level()) {
+                return Err(LevelUpError::InvalidSpell(spell.id()));
+            }
+        }
+
+        // Skill counts are left to `add_level`, for its own errors.
+        match find(|c| matches!(c, Choice::Skills { .. })) {
+            Some(choice) if self.skills.is_empty() => Err(LevelUpError::Missing(choice)),
+            _ => Ok(()),
+        }
+    }
+}
@@ -0,0 +1,508 @@
+//!
+//! Classes, subclasses and multiclassing.
+//!
+//! ### Examples
+//! ***
+//! A fighter, multiclassing into wizard:
+//! ```
+//! use xander::{
+//!     ability::{skills::{Athletics, Perception}, Intelligence, Strength},
+//!     class::{Fighter, Wizard},
+//!     creature::Creature,
+//!     identity::Identity,
+//! };
+//!
+//! let mut ent = Creature::default();
+//! ent.stats().extend([(Strength.id(), 15), (Intelligence.id(), 13)]);
+//!
+//! ent.add_level(&Fighter, &[Athletics.id(), Perception.id()]).unwrap();
+//! ent.add_level(&Wizard, &[]).unwrap();
+//!
+//! assert_eq!(ent.level(), 2);
+//! assert_eq!(ent.class_level(Wizard.id()), 1);
+//! ```
+//!
+
+mod bard;
+mod fighter;
+mod level_up;
+mod rogue;
+mod wizard;
+
+pub use bard::Bard;
+pub use fighter::{Champion, Fighter};
+pub use level_up::{Choice, HitPoints, Improvement, LevelUp, LevelUpError};
+pub use rogue::Rogue;
+pub use wizard::{Evocation, Wizard};
+
+use crate::{
+    creature::proficiency::Offer, feature::Feature, identity::Identity, spell::slots::Caster,
+};
+
+///
+/// Highest total character level.
+///
+pub const MAX_LEVEL: usize = 20;
+
+///
+/// Ability score minimums, any one of which is enough
+/// (e.g. STR 13 *or* DEX 13 for fighters).
+///
+pub type Prerequisite = Vec<(&'static str, usize)>;
+
+pub trait Class: Identity {
+    ///
+    /// Number of sides on this class's hit die.
+    ///
+    fn hit_die(&self) -> usize;
+
+    ///
+    /// Saving throw proficiencies, only
+    /// granted when this is the first class.
+    ///
+    fn saves(&self) -> Vec<Offer>;
+
+    ///
+    /// How many skills to pick, and from which.
+    ///
+    fn skills(&self) -> (usize, Vec<Offer>);
+
+    ///
+    /// Armor, weapon and tool proficiencies at first level.
+    ///
+    fn proficiencies(&self) -> Vec<Offer>;
+
+    ///
+    /// Ability score minimums to multiclass
+    /// into (or out of) this class.
+    ///
+    fn prerequisites(&self) -> Vec<Prerequisite>;
+
+    ///
+    /// Proficiencies gained when multiclassing into this class.
+    ///
+    fn multiclass_proficiencies(&self) -> Vec<Offer>;
+
+    ///
+    /// Skills picked when multiclassing into this class,
+    /// from the usual [Class::skills] list.
+    ///
+    fn multiclass_skills(&self) -> usize {
+        0
+    }
+
+    ///
+    /// Class level at which a subclass is chosen.
+    ///
+    fn subclass_level(&self) -> usize {
+        3
+    }
+
+    ///
+    /// Class levels granting an Ability Score Improvement (or a feat).
+    ///
+    fn improvements(&self) -> Vec<usize> {
+        vec![4, 8, 12, 16, 19]
+    }
+
+    ///
+    /// Features gained on reaching `level` in this class.
+    ///
+    fn features(&self, level: usize) -> Vec<Box<dyn Feature>>;
+
+    ///
+    /// New spells learned on reaching `level` in this class.
+    ///
+    fn spells_learned(&self, level: usize) -> usize {
+        let _ = level;
+        0
+    }
+
+    ///
+    /// Spellcasting feature at `level`, if any.
+    ///
+    fn caster(&self, level: usize) -> Option<Caster> {
+        let _ = level;
+        None
+    }
+}
+
+pub trait Subclass: Identity {
+    ///
+    /// Id of the class this belongs to.
+    ///
+    fn class(&self) -> &'static str;
+
+    ///
+    /// Features gained on reaching `level` in the class.
+    ///
+    fn features(&self, level: usize) -> Vec<Box<dyn Feature>>;
+}
+
+///
+/// Levels taken in a single class.
+///
+#[derive(Debug, Clone, Copy)]
+pub struct ClassLevel {
+    class: &'static dyn Class,
+    level: usize,
+    subclass: Option<&'static dyn Subclass>,
+}
+
+impl ClassLevel {
+    pub fn new(class: &'static dyn Class) -> Self {
+        Self {
+            class,
+            level: 0,
+            subclass: None,
+        }
+    }
+
+    pub fn class(&self) -> &'static dyn Class {
+        self.class
+    }
+
+    pub fn level(&self) -> usize {
+        self.level
+    }
+
+    pub fn subclass(&self) -> Option<&'static dyn Subclass> {
+        self.subclass
+    }
+
+    pub(crate) fn set_level(&mut self, level: usize) {
+        self.level = level;
+    }
+
+    pub(crate) fn set_subclass(&mut self, subclass: Option<&'static dyn Subclass>) {
+        self.subclass = subclass;
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ClassError {
+    ///
+    /// Already at [MAX_LEVEL].
+    ///
+    MaxLevel,
+    ///
+    /// Ability scores too low to multiclass
+    /// into or out of this class.
+    ///
+    Prerequisite(&'static str),
+    ///
+    /// Wrong number of skills picked.
+    ///
+    SkillCount { expected: usize, got: usize },
+    ///
+    /// Skill not on offer, or picked twice.
+    ///
+    InvalidSkill(&'static str),
+    ///
+    /// No levels in the subclass's class.
+    ///
+    MissingClass(&'static str),
+    ///
+    /// Class level too low for a subclass.
+    ///
+    SubclassLevel { required: usize },
+    ///
+    /// A subclass was already chosen.
+    ///
+    SubclassTaken(&'static str),
+}
+
+///
+/// Does `scores` meet every prerequisite of `class`?
+///
+pub fn meets(class: &dyn Class, score: impl Fn(&str) -> usize) -> bool {
+    class
+        .prerequisites()
+        .iter()
+        .all(|any| any.iter().any(|(ability, min)| score(ability) >= *min))
+}
+
+///
+/// Pick `count` of `offers`, by id.
+///
+pub fn pick<'o>(
+    offers: &'o [Offer],
+    count: usize,
+    picked: &[&'static str],
+) -> Result<Vec<&'o Offer>, ClassError> {
+    if picked.len() != count {
+        return Err(ClassError::SkillCount {
+            expected: count,
+            got: picked.len(),
+        });
+    }
+
+    picked
+        .iter()
+        .enumerate()
+        .map(|(i, id)| {
+            match picked[..i].contains(id) {
+                true => None,
+                false => offers.iter().find(|o| o.id() == *id),
+            }
+            .ok_or(ClassError::InvalidSkill(id))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        ability::{
+            skills::{Acrobatics, Arcana, Athletics, History, Perception, Stealth},
+            Charisma, Checks, Constitution, Dexterity, Intelligence, Saves, Strength,
+        },
+        creature::{proficiency::Source, Creature},
+        feature::{
+            ActionSurge, Alert, JackOfAllTrades, ReliableTalent, RemarkableAthlete, SecondWind,
+        },
+        identity::Identity,
+        item::{
+            armor::{Armor as _, ArmorCategory, Plate},
+            tool::ThievesTools,
+            weapon::{Longsword, Weapon, WeaponCategory},
+            Armor, Tools, Weapons,
+        },
+        spell::{
+            Bane, Bless, BurningHands, DetectMagic, FireBolt, Fireball, GuidingBolt, HoldPerson,
+            MagicMissile, Spell,
+        },
+    };
+
+    use super::{
+        Bard, Champion, Choice, ClassError, Evocation, Fighter, HitPoints, Improvement, LevelUp,
+        LevelUpError, Rogue, Wizard,
+    };
+
+    #[test]
+    fn first_class() {
+        let mut ent = Creature::default();
+
+        assert_eq!(
+            ent.add_level(&Fighter, &[Athletics.id()]),
+            Err(ClassError::SkillCount {
+                expected: 2,
+                got: 1
+            })
+        );
+        assert_eq!(
+            ent.add_level(&Fighter, &[Athletics.id(), Stealth.id()]),
+            Err(ClassError::InvalidSkill(Stealth.id()))
+        );
+        assert_eq!(
+            ent.add_level(&Fighter, &[Athletics.id(), Athletics.id()]),
+            Err(ClassError::InvalidSkill(Athletics.id()))
+        );
+        assert_eq!(ent.level(), 0);
+
+        ent.add_level(&Fighter, &[Athletics.id(), Perception.id()])
+            .unwrap();
+
+        assert!(ent.proficient(Saves(Strength)).is_some());
+        assert!(ent.proficient(Checks(Perception)).is_some());
+        assert!(ent.proficient(Armor(ArmorCategory::Heavy)).is_some());
+        assert!(Longsword.proficient(&ent));
+        assert_eq!(
+            ent.explain(Checks(Athletics))[0].source(),
+            Source::Class(Fighter.id())
+        );
+    }
+
+    #[test]
+    fn multiclassing() {
+        let mut ent = Creature::default();
+        ent.stats().extend([
+            (Strength.id(), 15),
+            (Dexterity.id(), 14),
+            (Intelligence.id(), 12),
+            (Charisma.id(), 8),
+        ]);
+        ent.add_level(&Fighter, &[Athletics.id(), Perception.id()])
+            .unwrap();
+
+        // CHA 8: no bard, INT 12: no wizard.
+        assert_eq!(
+            ent.add_level(&Bard, &[Arcana.id()]),
+            Err(ClassError::Prerequisite(Bard.id()))
+        );
+        assert_eq!(
+            ent.add_level(&Wizard, &[]),
+            Err(ClassError::Prerequisite(Wizard.id()))
+        );
+
+        ent.add_level(&Rogue, &[Acrobatics.id()]).unwrap();
+
+        // Only the multiclass subset: no DEX save, no heavy armor from rogue.
+        assert!(ent.proficient(Saves(Dexterity)).is_none());
+        assert!(ent.proficient(Tools(ThievesTools)).is_some());
+        assert!(ent.proficient(Checks(Acrobatics)).is_some());
+        assert!(ent.proficient(Weapons(WeaponCategory::Martial)).is_some());
+
+        assert_eq!(ent.level(), 2);
+        assert_eq!(ent.class_level(Rogue.id()), 1);
+        assert_eq!(ent.classes()[0].class().hit_die(), 10);
+
+        // Skills only on the first level of a class.
+        assert_eq!(
+            ent.add_level(&Rogue, &[History.id()]),
+            Err(ClassError::SkillCount {
+                expected: 0,
+                got: 1
+            })
+        );
+    }
+
+    #[test]
+    fn features_and_subclasses() {
+        let mut ent = Creature::default();
+        ent.add_level(&Fighter, &[Athletics.id(), Perception.id()])
+            .unwrap();
+
+        assert_eq!(
+            ent.set_subclass(&Champion),
+            Err(ClassError::SubclassLevel { required: 3 })
+        );
+
+        for level in 2..=7 {
+            assert_eq!(ent.add_level(&Fighter, &[]), Ok(level));
+        }
+        assert!(!ent.features().any(|f| f.id() == RemarkableAthlete.id()));
+        assert_eq!(ent.spend(ActionSurge.id()), Ok(0));
+        assert_eq!(ent.uses(SecondWind.id()), Some((1, 1)));
+
+        // Features from earlier levels catch up.
+        ent.set_subclass(&Champion).unwrap();
+        assert!(ent.features().any(|f| f.id() == RemarkableAthlete.id()));
+        assert_eq!(
+            ent.set_subclass(&Champion),
+            Err(ClassError::SubclassTaken(Champion.id()))
+        );
+
+        let mut bard = Creature::default();
+        bard.stats().insert(Charisma.id(), 16);
+        bard.add_level(&Bard, &[Arcana.id(), History.id(), Stealth.id()])
+            .unwrap();
+        bard.add_level(&Bard, &[]).unwrap();
+        assert!(bard.features().any(|f| f.id() == JackOfAllTrades.id()));
+        assert_eq!(bard.spellcasting().caster_level(), 2);
+        assert!(!bard.features().any(|f| f.id() == ReliableTalent.id()));
+
+        assert!(Plate.proficient(&ent));
+    }
+
+    #[test]
+    fn level_up() {
+        let mut ent = Creature::default();
+        ent.stats()
+            .extend([(Strength.id(), 15), (Constitution.id(), 14)]);
+        ent.level_up(LevelUp::new(&Fighter).skills(&[Athletics.id(), Perception.id()]))
+            .unwrap();
+        ent.level_up(LevelUp::new(&Fighter)).unwrap();
+
+        // 10 + 2, then 6 + 2 per level.
+        assert_eq!(ent.max_hp(), 20);
+        assert_eq!(
+            ent.level_up(LevelUp::new(&Fighter)),
+            Err(LevelUpError::Missing(Choice::Subclass))
+        );
+        ent.level_up(LevelUp::new(&Fighter).subclass(&Champion))
+            .unwrap();
+        assert_eq!(ent.max_hp(), 28);
+
+        assert_eq!(
+            LevelUp::pending(&ent, &Fighter),
+            [Choice::HitPoints(10), Choice::Improvement]
+        );
+        for scores in [
+            vec![(Strength.id(), 1)],
+            vec![(Strength.id(), 1), (Strength.id(), 1)],
+        ] {
+            assert_eq!(
+                ent.level_up(LevelUp::new(&Fighter).improvement(Improvement::Scores(scores))),
+                Err(LevelUpError::InvalidScores)
+            );
+        }
+        assert_eq!(ent.level(), 3);
+
+        let roll = LevelUp::new(&Fighter)
+            .hit_points(HitPoints::Roll)
+            .improvement(Improvement::Scores(vec![(Constitution.id(), 2)]));
+        ent.level_up(roll).unwrap();
+
+        // CON 16 now, for every level.
+        assert!((1..=10).contains(&ent.hp_rolls()[3]));
+        assert_eq!(ent.max_hp(), 22 + 4 * 3 + ent.hp_rolls()[3]);
+
+        ent.undo_level_up();
+        assert_eq!(ent.score::<Constitution>(Constitution), Some(14));
+        ent.undo_level_up();
+        assert!(ent.classes()[0].subclass().is_none());
+
+        ent.level_up(LevelUp::new(&Fighter).subclass(&Champion))
+            .unwrap();
+        ent.level_up(LevelUp::new(&Fighter).improvement(Improvement::feat(Alert)))
+            .unwrap();
+        assert!(ent.features().any(|f| f.id() == Alert.id()));
+
+        while ent.undo_level_up().is_some() {}
+        assert_eq!(ent.level(), 0);
+        assert_eq!(ent.max_hp(), 0);
+        assert!(ent.features().next().is_none());
+        assert!(ent.resource(SecondWind.id()).is_none());
+        assert!(ent.proficient(Saves(Strength)).is_none());
+    }
+
+    #[test]
+    fn learning_spells() {
+        let mut ent = Creature::default();
+        ent.stats().insert(Intelligence.id(), 16);
+
+        let spells: [&'static dyn Spell; 6] = [
+            &BurningHands,
+            &MagicMissile,
+            &DetectMagic,
+            &Bless,
+            &Bane,
+            &GuidingBolt,
+        ];
+        let wizard = || LevelUp::new(&Wizard).skills(&[Arcana.id(), History.id()]);
+
+        assert_eq!(
+            ent.level_up(wizard().spells(&spells[..5])),
+            Err(LevelUpError::SpellCount {
+                expected: 6,
+                got: 5
+            })
+        );
+        // Too high a level, a cantrip, and picked twice.
+        for invalid in [&Fireball as &'static dyn Spell, &FireBolt, &Bless] {
+            let picked = [&spells[..5], &[invalid]].concat();
+            assert_eq!(
+                ent.level_up(wizard().spells(&picked)),
+                Err(LevelUpError::InvalidSpell(invalid.id()))
+            );
+        }
+        assert_eq!(ent.level(), 0);
+
+        ent.level_up(wizard().spells(&spells)).unwrap();
+        assert_eq!(ent.spells().len(), 6);
+
+        // Already known.
+        let second = LevelUp::new(&Wizard)
+            .subclass(&Evocation)
+            .spells(&[&MagicMissile, &HoldPerson]);
+        assert_eq!(
+            ent.level_up(second),
+            Err(LevelUpError::InvalidSpell(MagicMissile.id()))
+        );
+
+        ent.undo_level_up();
+        assert!(ent.spells().is_empty());
+        assert_eq!(ent.spellcasting().caster_level(), 0);
+    }
+}
@@ -0,0 +1,103 @@
+use xander_macros::identify;
+
+use crate::{
+    ability::{
+        skills::{
+            Acrobatics, Athletics, Deception, Insight, Intimidation, Investigation, Perception,
+            Performance, Persuasion, SleightOfHand, Stealth,
+        },
+        Checks, Dexterity, Intelligence, Saves,
+    },
+    creature::proficiency::Offer,
+    feature::{Evasion, Feature, ReliableTalent},
+    identity::Identity,
+    item::{
+        armor::ArmorCategory,
+        tool::ThievesTools,
+        weapon::{HandCrossbow, Longsword, Rapier, Shortsword, WeaponCategory},
+        Armor, Tools, Weapons,
+    },
+};
+
+use super::{Class, Prerequisite};
+
+///
+/// Scoundrels who rely on stealth and skill.
+///
+/// Expertise is a choice, and is left to the caller.
+///
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
+pub struct Rogue;
+
+identify!(Rogue, "5E::CLASS::ROGUE");
+
+impl Class for Rogue {
+    fn hit_die(&self) -> usize {
+        8
+    }
+
+    fn saves(&self) -> Vec<Offer> {
+        vec![
+            Offer::new(Saves(Dexterity)),
+            Offer::new(Saves(Intelligence)),
+        ]
+    }
+
+    fn skills(&self) -> (usize, Vec<Offer>) {
+        (
+            4,
+            vec![
+                Offer::new(Checks(Acrobatics)),
+                Offer::new(Checks(Athletics)),
+                Offer::new(Checks(Deception)),
+                Offer::new(Checks(Insight)),
+                Offer::new(Checks(Intimidation)),
+                Offer::new(Checks(Investigation)),
+                Offer::new(Checks(Perception)),
+                Offer::new(Checks(Performance)),
+                Offer::new(Checks(Persuasion)),
+                Offer::new(Checks(SleightOfHand)),
+                Offer::new(Checks(Stealth)),
+            ],
+        )
+    }
+
+    fn proficiencies(&self) -> Vec<Offer> {
+        vec![
+            Offer::new(Armor(ArmorCategory::Light)),
+            Offer::new(Weapons(WeaponCategory::Simple)),
+            Offer::new(Weapons(HandCrossbow)),
+            Offer::new(Weapons(Longsword)),
+            Offer::new(Weapons(Rapier)),
+            Offer::new(Weapons(Shortsword)),
+            Offer::new(Tools(ThievesTools)),
+        ]
+    }
+
+    fn prerequisites(&self) -> Vec<Prerequisite> {
+        vec![vec![(Dexterity.id(), 13)]]
+    }
+
+    fn multiclass_proficiencies(&self) -> Vec<Offer> {
+        vec![
+            Offer::new(Armor(ArmorCategory::Light)),
+            Offer::new(Tools(ThievesTools)),
+        ]
+    }
+
+    fn multiclass_skills(&self) -> usize {
+        1
+    }
+
+    fn improvements(&self) -> Vec<usize> {
+        vec![4, 8, 10, 12, 16, 19]
+    }
+
+    fn features(&self, level: usize) -> Vec<Box<dyn Feature>> {
+        match level {
+            7 => vec![Box::new(Evasion)],
+            11 => vec![Box::new(ReliableTalent)],
+            _ => vec![],
+        }
+    }
+}
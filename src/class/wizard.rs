@@ -0,0 +1,115 @@
+use xander_macros::identify;
+
+use crate::{
+    ability::{
+        skills::{Arcana, History, Insight, Investigation, Medicine, Religion},
+        Checks, Intelligence, Saves, Wisdom,
+    },
+    creature::proficiency::Offer,
+    feature::Feature,
+    identity::Identity,
+    item::{
+        weapon::{Dagger, Dart, LightCrossbow, Quarterstaff, Sling},
+        Weapons,
+    },
+    spell::slots::{Caster, Progression},
+};
+
+use super::{Class, Prerequisite, Subclass};
+
+///
+/// Scholarly magic-users.
+///
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
+pub struct Wizard;
+
+identify!(Wizard, "5E::CLASS::WIZARD");
+
+impl Class for Wizard {
+    fn hit_die(&self) -> usize {
+        6
+    }
+
+    fn saves(&self) -> Vec<Offer> {
+        vec![Offer::new(Saves(Intelligence)), Offer::new(Saves(Wisdom))]
+    }
+
+    fn skills(&self) -> (usize, Vec<Offer>) {
+        (
+            2,
+            vec![
+                Offer::new(Checks(Arcana)),
+                Offer::new(Checks(History)),
+                Offer::new(Checks(Insight)),
+                Offer::new(Checks(Investigation)),
+                Offer::new(Checks(Medicine)),
+                Offer::new(Checks(Religion)),
+            ],
+        )
+    }
+
+    fn proficiencies(&self) -> Vec<Offer> {
+        vec![
+            Offer::new(Weapons(Dagger)),
+            Offer::new(Weapons(Dart)),
+            Offer::new(Weapons(Sling)),
+            Offer::new(Weapons(Quarterstaff)),
+            Offer::new(Weapons(LightCrossbow)),
+        ]
+    }
+
+    fn prerequisites(&self) -> Vec<Prerequisite> {
+        vec![vec![(Intelligence.id(), 13)]]
+    }
+
+    fn multiclass_proficiencies(&self) -> Vec<Offer> {
+        vec![]
+    }
+
+    fn subclass_level(&self) -> usize {
+        2
+    }
+
+    fn features(&self, _: usize) -> Vec<Box<dyn Feature>> {
+        vec![]
+    }
+
+    ///
+    /// Six spells in the spellbook at first level, then two per level.
+    ///
+    fn spells_learned(&self, level: usize) -> usize {
+        match level {
+            1 => 6,
+            _ => 2,
+        }
+    }
+
+    fn caster(&self, level: usize) -> Option<Caster> {
+        Some(Caster::new(
+            self.id(),
+            Intelligence,
+            Progression::Full,
+            level,
+        ))
+    }
+}
+
+///
+/// Arcane Tradition of damaging spells.
+///
+/// None of its features are modeled yet.
+///
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
+pub struct Evocation;
+
+identify!(Evocation, "5E::SUBCLASS::EVOCATION");
+
+impl Subclass for Evocation {
+    fn class(&self) -> &'static str {
+        Wizard.id()
+    }
+
+    fn features(&self, _: usize) -> Vec<Box<dyn Feature>> {
+        vec![]
+    }
+}
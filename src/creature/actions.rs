@@ -0,0 +1,184 @@
+///
+/// What a creature can spend on its turn.
+///
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
+pub enum ActionKind {
+    Action,
+    BonusAction,
+    ///
+    /// Usable off-turn, refreshes at the
+    /// start of the creature's own turn.
+    ///
+    Reaction,
+    ///
+    /// The one free interaction with an object
+    /// (e.g. drawing a sword) each turn.
+    ///
+    ObjectInteraction,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ActionError {
+    ///
+    /// Already used this turn (or round, for reactions).
+    ///
+    Spent(ActionKind),
+    ///
+    /// Only reactions can be taken outside one's own turn.
+    ///
+    NotYourTurn(ActionKind),
+    ///
+    /// Movement is only spent on one's own turn.
+    ///
+    OffTurnMovement,
+    ///
+    /// Not enough movement left this turn.
+    ///
+    NoMovement { needed: u32, left: u32 },
+}
+
+///
+/// Action economy of a creature, over a single turn.
+///
+/// ***
+/// ```
+/// use xander::creature::actions::{ActionError, ActionKind, Economy};
+///
+/// let mut turn = Economy::default();
+/// turn.start_turn(30);
+///
+/// assert_eq!(turn.take(ActionKind::Action), Ok(()));
+/// assert_eq!(turn.take(ActionKind::Action), Err(ActionError::Spent(ActionKind::Action)));
+/// assert_eq!(turn.spend_movement(20), Ok(10));
+/// ```
+///
+#[derive(Debug, Default, Clone)]
+pub struct Economy {
+    on_turn: bool,
+    speed: u32,
+    movement: u32,
+    spent: Vec<ActionKind>,
+}
+
+impl Economy {
+    ///
+    /// Refresh everything, including the reaction.
+    ///
+    pub fn start_turn(&mut self, speed: u32) {
+        self.on_turn = true;
+        self.speed = speed;
+        self.movement = speed;
+        self.spent.clear();
+    }
+
+    ///
+    /// Unused movement is lost, but the
+    /// reaction stays available until next turn.
+    ///
+    pub fn end_turn(&mut self) {
+        self.on_turn = false;
+        self.movement = 0;
+    }
+
+    pub fn on_turn(&self) -> bool {
+        self.on_turn
+    }
+
+    pub fn available(&self, kind: ActionKind) -> bool {
+        self.check(kind).is_ok()
+    }
+
+    fn check(&self, kind: ActionKind) -> Result<(), ActionError> {
+        if kind != ActionKind::Reaction && !self.on_turn {
+            return Err(ActionError::NotYourTurn(kind));
+        }
+
+        match self.spent.contains(&kind) {
+            true => Err(ActionError::Spent(kind)),
+            false => Ok(()),
+        }
+    }
+
+    ///
+    /// Use up an action, bonus action, reaction or object interaction.
+    ///
+    pub fn take(&mut self, kind: ActionKind) -> Result<(), ActionError> {
+        self.check(kind)?;
+        self.spent.push(kind);
+        Ok(())
+    }
+
+    ///
+    /// Movement left this turn, in feet.
+    ///
+    pub fn movement(&self) -> u32 {
+        self.movement
+    }
+
+    ///
+    /// Move `feet`, returning the movement left.
+    ///
+    pub fn spend_movement(&mut self, feet: u32) -> Result<u32, ActionError> {
+        if !self.on_turn {
+            return Err(ActionError::OffTurnMovement);
+        }
+
+        match self.movement.checked_sub(feet) {
+            Some(left) => {
+                self.movement = left;
+                Ok(left)
+            }
+            None => Err(ActionError::NoMovement {
+                needed: feet,
+                left: self.movement,
+            }),
+        }
+    }
+
+    ///
+    /// Dash: spend the action to gain extra movement
+    /// equal to the creature's speed.
+    ///
+    pub fn dash(&mut self) -> Result<u32, ActionError> {
+        self.take(ActionKind::Action)?;
+        self.movement += self.speed;
+        Ok(self.movement)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ActionError, ActionKind::*, Economy};
+
+    #[test]
+    fn turn_economy() {
+        let mut turn = Economy::default();
+
+        assert_eq!(turn.take(Action), Err(ActionError::NotYourTurn(Action)));
+        assert_eq!(turn.take(Reaction), Ok(()));
+        assert_eq!(turn.spend_movement(5), Err(ActionError::OffTurnMovement));
+
+        // Reaction refreshes with the turn.
+        turn.start_turn(30);
+        assert!(turn.available(Reaction));
+        assert_eq!(turn.take(BonusAction), Ok(()));
+        assert_eq!(turn.take(BonusAction), Err(ActionError::Spent(BonusAction)));
+        assert_eq!(turn.take(ObjectInteraction), Ok(()));
+
+        assert_eq!(turn.spend_movement(25), Ok(5));
+        assert_eq!(
+            turn.spend_movement(10),
+            Err(ActionError::NoMovement {
+                needed: 10,
+                left: 5
+            })
+        );
+        assert_eq!(turn.dash(), Ok(35));
+        assert_eq!(turn.dash(), Err(ActionError::Spent(Action)));
+
+        turn.end_turn();
+        assert_eq!(turn.movement(), 0);
+        assert_eq!(turn.take(Reaction), Ok(()));
+        assert_eq!(turn.take(Reaction), Err(ActionError::Spent(Reaction)));
+    }
+}
@@ -0,0 +1,92 @@
+use crate::spell::Spell;
+
+///
+/// The spell a creature is currently concentrating on.
+///
+/// A creature can only concentrate on one spell at a time.
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Concentration {
+    spell: &'static str,
+}
+
+impl Concentration {
+    pub fn new(spell: &dyn Spell) -> Self {
+        Self { spell: spell.id() }
+    }
+
+    ///
+    /// Id of the spell being concentrated on.
+    ///
+    pub fn spell(&self) -> &'static str {
+        self.spell
+    }
+}
+
+///
+/// Why concentration on a spell ended.
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Reason {
+    ///
+    /// Failed the CON save after taking damage.
+    ///
+    FailedSave {
+        roll: i32,
+        dc: i32,
+    },
+    Incapacitated,
+    ///
+    /// Started concentrating on another spell.
+    ///
+    Replaced(&'static str),
+    ///
+    /// Ended voluntarily.
+    ///
+    Dropped,
+}
+
+///
+/// Concentration changes, for the UI to pick up
+/// (see [super::Creature::events]).
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Event {
+    Started(&'static str),
+    ///
+    /// Took damage, but passed the CON save.
+    ///
+    Maintained {
+        spell: &'static str,
+        roll: i32,
+        dc: i32,
+    },
+    Ended(&'static str, Reason),
+}
+
+///
+/// Tag on the [super::context::CheckContext] of
+/// saves made to keep concentrating.
+///
+pub const TAG: &str = "5E::TAG::CONCENTRATION";
+
+///
+/// DC of the CON save to keep concentrating,
+/// after taking `damage`: 10, or half the damage if higher.
+///
+pub fn dc(damage: usize) -> i32 {
+    10.max(damage as i32 / 2)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::dc;
+
+    #[test]
+    fn save_dc() {
+        assert_eq!(dc(1), 10);
+        assert_eq!(dc(21), 10);
+        assert_eq!(dc(22), 11);
+        assert_eq!(dc(45), 22);
+    }
+}
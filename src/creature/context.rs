@@ -0,0 +1,249 @@
+use std::collections::HashSet;
+
+use crate::{
+    dice::{
+        modifiers::{Advantage, Disadvantage, Modifier},
+        Rolls, D20,
+    },
+    identity::Identity,
+    item::tool::Tool,
+};
+
+use super::Creature;
+
+///
+/// The circumstances a check (or save) is made in.
+///
+/// Handed to [super::proficiency::ProficiencyType]s and
+/// [Situational] modifiers, so they can apply selectively.
+///
+/// ***
+/// ```
+/// use xander::{creature::context::CheckContext, item::tool::ThievesTools};
+///
+/// let ctx = CheckContext::default()
+///     .using(ThievesTools)
+///     .tag("5E::TAG::TRAP");
+///
+/// assert!(ctx.uses(&ThievesTools));
+/// ```
+///
+#[derive(Debug, Default, Clone)]
+pub struct CheckContext {
+    check: Option<&'static str>,
+    ability: Option<&'static str>,
+    tools: HashSet<&'static str>,
+    tags: HashSet<&'static str>,
+    advantage: bool,
+    disadvantage: bool,
+}
+
+///
+/// How the d20 of a check is rolled.
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RollMode {
+    Normal,
+    Advantage,
+    Disadvantage,
+}
+
+impl CheckContext {
+    ///
+    /// Record the check being made, and its base ability.
+    ///
+    /// Filled in by the [Creature] making the roll.
+    ///
+    pub fn making(mut self, check: &dyn Identity, ability: &dyn Identity) -> Self {
+        self.check = Some(check.id());
+        self.ability = Some(ability.id());
+        self
+    }
+
+    ///
+    /// A tool used for this check.
+    ///
+    pub fn using(mut self, tool: impl Tool) -> Self {
+        self.tools.insert(tool.id());
+        self
+    }
+
+    ///
+    /// Free-form tag for the situation
+    /// (e.g. `"5E::TAG::STONEWORK"`).
+    ///
+    pub fn tag(mut self, tag: &'static str) -> Self {
+        self.tags.insert(tag);
+        self
+    }
+
+    ///
+    /// Roll with advantage.
+    ///
+    pub fn advantage(mut self) -> Self {
+        self.advantage = true;
+        self
+    }
+
+    ///
+    /// Roll with disadvantage.
+    ///
+    pub fn disadvantage(mut self) -> Self {
+        self.disadvantage = true;
+        self
+    }
+
+    ///
+    /// Advantage and disadvantage cancel out,
+    /// however many sources of each there are.
+    ///
+    pub fn mode(&self) -> RollMode {
+        match (self.advantage, self.disadvantage) {
+            (true, false) => RollMode::Advantage,
+            (false, true) => RollMode::Disadvantage,
+            _ => RollMode::Normal,
+        }
+    }
+
+    ///
+    /// Roll the d20 for this check.
+    ///
+    pub fn d20(&self) -> Rolls {
+        match self.mode() {
+            RollMode::Normal => D20(),
+            RollMode::Advantage => D20(2).then(Advantage(D20)),
+            RollMode::Disadvantage => D20(2).then(Disadvantage(D20)),
+        }
+    }
+
+    ///
+    /// Id of the check (or save) being made.
+    ///
+    pub fn check(&self) -> Option<&'static str> {
+        self.check
+    }
+
+    ///
+    /// Id of the ability behind the check.
+    ///
+    pub fn ability(&self) -> Option<&'static str> {
+        self.ability
+    }
+
+    pub fn is(&self, check: &dyn Identity) -> bool {
+        self.check == Some(check.id())
+    }
+
+    pub fn uses(&self, tool: &dyn Tool) -> bool {
+        self.tools.contains(tool.id())
+    }
+
+    pub fn tagged(&self, tag: &str) -> bool {
+        self.tags.contains(tag)
+    }
+}
+
+///
+/// Builds a dice [Modifier] for a roll, if
+/// the circumstances call for one.
+///
+pub type ModifierFn = dyn Fn(&Creature, &CheckContext) -> Option<Box<dyn Modifier>>;
+
+///
+/// A modifier that only applies to some rolls,
+/// e.g. a +2 on Perception checks that rely on smell.
+///
+pub struct Situational {
+    id: &'static str,
+    modifier: Box<ModifierFn>,
+}
+
+impl Situational {
+    pub fn new(
+        id: &'static str,
+        modifier: impl Fn(&Creature, &CheckContext) -> Option<Box<dyn Modifier>> + 'static,
+    ) -> Self {
+        Self {
+            id,
+            modifier: Box::new(modifier),
+        }
+    }
+
+    pub fn id(&self) -> &'static str {
+        self.id
+    }
+
+    pub fn modifier(&self, ent: &Creature, ctx: &CheckContext) -> Option<Box<dyn Modifier>> {
+        (self.modifier)(ent, ctx)
+    }
+}
+
+///
+/// Decides whether an [Edge] applies to a roll.
+///
+pub type PredicateFn = dyn Fn(&Creature, &CheckContext) -> bool;
+
+///
+/// A source of advantage or disadvantage on some rolls,
+/// e.g. advantage on initiative from Feral Instinct.
+///
+pub struct Edge {
+    id: &'static str,
+    mode: RollMode,
+    when: Box<PredicateFn>,
+}
+
+impl Edge {
+    pub fn advantage(
+        id: &'static str,
+        when: impl Fn(&Creature, &CheckContext) -> bool + 'static,
+    ) -> Self {
+        Self {
+            id,
+            mode: RollMode::Advantage,
+            when: Box::new(when),
+        }
+    }
+
+    pub fn disadvantage(
+        id: &'static str,
+        when: impl Fn(&Creature, &CheckContext) -> bool + 'static,
+    ) -> Self {
+        Self {
+            id,
+            mode: RollMode::Disadvantage,
+            when: Box::new(when),
+        }
+    }
+
+    pub fn id(&self) -> &'static str {
+        self.id
+    }
+
+    pub fn mode(&self) -> RollMode {
+        self.mode
+    }
+
+    ///
+    /// Apply this edge onto `ctx`, if relevant.
+    ///
+    pub fn apply(&self, ent: &Creature, ctx: CheckContext) -> CheckContext {
+        match (self.mode, (self.when)(ent, &ctx)) {
+            (RollMode::Advantage, true) => ctx.advantage(),
+            (RollMode::Disadvantage, true) => ctx.disadvantage(),
+            _ => ctx,
+        }
+    }
+}
+
+impl std::fmt::Debug for Edge {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Edge({}, {:?})", self.id, self.mode)
+    }
+}
+
+impl std::fmt::Debug for Situational {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Situational({})", self.id)
+    }
+}
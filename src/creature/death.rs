@@ -0,0 +1,119 @@
+///
+/// Successes or failures needed to become stable, or to die.
+///
+pub const DEATH_SAVES: u8 = 3;
+
+///
+/// A death saving throw succeeds on 10 or higher.
+///
+pub const DC: i32 = 10;
+
+///
+/// Death saving throws made while at 0 hit points.
+///
+/// Reset on regaining any hit points.
+///
+#[derive(Debug, Clone, Copy, Default, Hash, PartialEq, Eq)]
+pub struct DeathSaves {
+    successes: u8,
+    failures: u8,
+}
+
+impl DeathSaves {
+    pub fn successes(&self) -> u8 {
+        self.successes
+    }
+
+    pub fn failures(&self) -> u8 {
+        self.failures
+    }
+
+    pub fn stable(&self) -> bool {
+        self.successes >= DEATH_SAVES && !self.dead()
+    }
+
+    pub fn dead(&self) -> bool {
+        self.failures >= DEATH_SAVES
+    }
+
+    pub(crate) fn succeed(&mut self) {
+        self.successes = (self.successes + 1).min(DEATH_SAVES);
+    }
+
+    pub(crate) fn fail(&mut self, times: u8) {
+        self.failures = (self.failures + times).min(DEATH_SAVES);
+    }
+
+    pub(crate) fn die(&mut self) {
+        self.failures = DEATH_SAVES;
+    }
+}
+
+///
+/// What came of a death saving throw.
+///
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
+pub enum DeathSave {
+    Success,
+    ///
+    /// Two failures on a natural 1.
+    ///
+    Failure(u8),
+    ///
+    /// Third success: no more saves needed.
+    ///
+    Stable,
+    Dead,
+    ///
+    /// Natural 20: back up with 1 hit point.
+    ///
+    Revived,
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::creature::Creature;
+
+    use super::DeathSave;
+
+    #[test]
+    fn dying() {
+        let mut ent = Creature::default();
+        ent.set_max_hp(Some(10));
+        assert_eq!(ent.death_save(), None);
+
+        ent.take_damage(10);
+        assert!(ent.dying());
+
+        // Damage at 0 hit points is a failure.
+        ent.take_damage(1);
+        ent.take_damage(1);
+        assert_eq!(ent.death_saves().failures(), 2);
+
+        let mut saves = 0;
+        while ent.dying() {
+            saves += 1;
+            match ent.death_save().unwrap() {
+                DeathSave::Revived => assert_eq!(ent.hp(), 1),
+                DeathSave::Dead => assert!(ent.dead()),
+                DeathSave::Stable => assert!(ent.death_saves().stable()),
+                _ => {}
+            }
+        }
+        assert!(saves <= 3);
+
+        // Healing the dying resets their saves.
+        let mut ent = Creature::default();
+        ent.set_max_hp(Some(10));
+        ent.take_damage(10);
+        ent.take_damage(1);
+        ent.heal(1);
+        assert_eq!((ent.hp(), ent.death_saves().failures()), (1, 0));
+
+        // Massive damage kills outright, and there's no healing that.
+        ent.take_damage(20);
+        assert!(ent.dead() && !ent.dying());
+        ent.heal(10);
+        assert_eq!(ent.hp(), 0);
+    }
+}
@@ -0,0 +1,263 @@
+use std::cell::Cell;
+
+use crate::dice::{Rolls, D4, D6};
+
+///
+/// Kinds of d20 test an [Effect] can apply to.
+///
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
+pub enum RollKind {
+    Attack,
+    Check,
+    Save,
+}
+
+///
+/// What an [Effect] does to a roll.
+///
+#[derive(Debug, Clone, Copy)]
+pub enum Bonus {
+    ///
+    /// Extra dice, added to the roll (e.g. Bless).
+    ///
+    Dice(fn() -> Rolls),
+    ///
+    /// Dice subtracted from the roll (e.g. Bane).
+    ///
+    Penalty(fn() -> Rolls),
+    Flat(i32),
+}
+
+impl Bonus {
+    pub fn apply(&self, rolls: Rolls) -> Rolls {
+        match self {
+            Bonus::Dice(dice) => rolls + dice(),
+            Bonus::Penalty(dice) => rolls - dice(),
+            Bonus::Flat(n) => rolls + *n,
+        }
+    }
+}
+
+///
+/// A time-bounded effect on a creature's rolls,
+/// like Bless or Bardic Inspiration.
+///
+/// ***
+/// ```
+/// use xander::creature::effect::{self, RollKind};
+///
+/// let bless = effect::bless("5E::CLASS::CLERIC");
+/// assert!(bless.applies(RollKind::Save));
+/// assert_eq!(bless.remaining(), Some(10));
+/// ```
+///
+#[derive(Debug, Clone)]
+pub struct Effect {
+    id: &'static str,
+    source: &'static str,
+    bonus: Bonus,
+    applies: Vec<RollKind>,
+    remaining: Option<u32>,
+    once: bool,
+    used: Cell<bool>,
+}
+
+impl Effect {
+    ///
+    /// A new effect, from `source` (e.g. the caster's class,
+    /// or a feature), lasting until removed.
+    ///
+    pub fn new(id: &'static str, source: &'static str, bonus: Bonus) -> Self {
+        Self {
+            id,
+            source,
+            bonus,
+            applies: vec![],
+            remaining: None,
+            once: false,
+            used: Cell::new(false),
+        }
+    }
+
+    ///
+    /// Apply to this kind of roll.
+    ///
+    pub fn on(mut self, kind: RollKind) -> Self {
+        self.applies.push(kind);
+        self
+    }
+
+    pub fn rounds(mut self, rounds: u32) -> Self {
+        self.remaining = Some(rounds);
+        self
+    }
+
+    ///
+    /// Lasts `minutes`, i.e. ten rounds each.
+    ///
+    pub fn minutes(self, minutes: u32) -> Self {
+        self.rounds(minutes * 10)
+    }
+
+    ///
+    /// Expires after applying to a single roll.
+    ///
+    pub fn once(mut self) -> Self {
+        self.once = true;
+        self
+    }
+
+    pub fn id(&self) -> &'static str {
+        self.id
+    }
+
+    pub fn source(&self) -> &'static str {
+        self.source
+    }
+
+    pub fn bonus(&self) -> Bonus {
+        self.bonus
+    }
+
+    pub fn applies(&self, kind: RollKind) -> bool {
+        self.applies.contains(&kind)
+    }
+
+    ///
+    /// Rounds left, `None` if it lasts until removed.
+    ///
+    pub fn remaining(&self) -> Option<u32> {
+        self.remaining
+    }
+
+    ///
+    /// Has it run out, either by time or by use?
+    ///
+    pub fn expired(&self) -> bool {
+        self.used.get() || self.remaining == Some(0)
+    }
+
+    ///
+    /// Apply to a roll, if of the right kind.
+    ///
+    /// Takes `&self`, so it can be used from within
+    /// checks and saves; single-use effects are marked spent.
+    ///
+    pub fn apply(&self, rolls: Rolls, kind: RollKind) -> Rolls {
+        if self.expired() || !self.applies(kind) {
+            return rolls;
+        }
+
+        self.used.set(self.once);
+        self.bonus.apply(rolls)
+    }
+
+    ///
+    /// One round goes by.
+    ///
+    pub fn tick(&mut self) {
+        if let Some(r) = self.remaining.as_mut() {
+            *r = r.saturating_sub(1);
+        }
+    }
+}
+
+///
+/// *Bless*: +1d4 to attacks and saves, for up to a minute.
+///
+pub fn bless(source: &'static str) -> Effect {
+    Effect::new("5E::EFFECT::BLESS", source, Bonus::Dice(|| D4(1)))
+        .on(RollKind::Attack)
+        .on(RollKind::Save)
+        .minutes(1)
+}
+
+///
+/// *Bane*: -1d4 to attacks and saves, for up to a minute.
+///
+pub fn bane(source: &'static str) -> Effect {
+    Effect::new("5E::EFFECT::BANE", source, Bonus::Penalty(|| D4(1)))
+        .on(RollKind::Attack)
+        .on(RollKind::Save)
+        .minutes(1)
+}
+
+///
+/// *Guidance*: +1d4 to a single check, within a minute.
+///
+pub fn guidance(source: &'static str) -> Effect {
+    Effect::new("5E::EFFECT::GUIDANCE", source, Bonus::Dice(|| D4(1)))
+        .on(RollKind::Check)
+        .minutes(1)
+        .once()
+}
+
+///
+/// *Bardic Inspiration*: a die added to one attack,
+/// check or save within 10 minutes.
+///
+/// The die grows with bard level: see [bardic_inspiration_die].
+///
+pub fn bardic_inspiration(source: &'static str, die: fn() -> Rolls) -> Effect {
+    Effect::new("5E::EFFECT::BARDIC_INSPIRATION", source, Bonus::Dice(die))
+        .on(RollKind::Attack)
+        .on(RollKind::Check)
+        .on(RollKind::Save)
+        .minutes(10)
+        .once()
+}
+
+///
+/// Bardic Inspiration die for a bard of `level`.
+///
+pub fn bardic_inspiration_die(level: usize) -> fn() -> Rolls {
+    use crate::dice::{D10, D12, D8};
+
+    match level {
+        0..=4 => || D6(1),
+        5..=9 => || D8(1),
+        10..=14 => || D10(1),
+        _ => || D12(1),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::dice::{Rolls, D10, D20, D4};
+
+    use super::{bane, bardic_inspiration, bardic_inspiration_die, guidance, RollKind};
+
+    #[test]
+    fn once() {
+        let guidance = guidance("5E::CLASS::CLERIC");
+
+        let save = guidance.apply(D20(), RollKind::Save);
+        assert_eq!(save[D4].len(), 0);
+        assert!(!guidance.expired());
+
+        let check = guidance.apply(D20(), RollKind::Check);
+        assert_eq!(check[D4].len(), 1);
+        assert!(guidance.expired());
+
+        assert_eq!(guidance.apply(D20(), RollKind::Check)[D4].len(), 0);
+    }
+
+    #[test]
+    fn durations() {
+        let mut bane = bane("5E::CLASS::CLERIC");
+        let mut fixed = Rolls::default();
+        fixed.add(&D20, [10].map(Into::into));
+        let attack = bane.apply(fixed, RollKind::Attack);
+        assert!(attack[D4][0].negated());
+        assert!((6..=9).contains(&attack.total()));
+
+        (0..9).for_each(|_| bane.tick());
+        assert!(!bane.expired());
+        bane.tick();
+        assert!(bane.expired());
+
+        let inspiration = bardic_inspiration("5E::CLASS::BARD", bardic_inspiration_die(10));
+        assert_eq!(inspiration.remaining(), Some(100));
+        assert_eq!(inspiration.apply(D20(), RollKind::Attack)[D10].len(), 1);
+    }
+}
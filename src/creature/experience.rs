@@ -0,0 +1,144 @@
+use crate::class::MAX_LEVEL;
+
+///
+/// Experience points needed for each character level.
+///
+pub const THRESHOLDS: [usize; MAX_LEVEL] = [
+    0, 300, 900, 2_700, 6_500, 14_000, 23_000, 34_000, 48_000, 64_000, 85_000, 100_000, 120_000,
+    140_000, 165_000, 195_000, 225_000, 265_000, 305_000, 355_000,
+];
+
+///
+/// Character level reached with `xp` experience points.
+///
+pub fn level(xp: usize) -> usize {
+    THRESHOLDS.iter().filter(|t| xp >= **t).count()
+}
+
+///
+/// Proficiency bonus at a character level:
+/// +2 at first level, and another +1 every four levels.
+///
+pub fn proficiency_bonus(level: usize) -> i32 {
+    2 + (level.clamp(1, MAX_LEVEL) as i32 - 1) / 4
+}
+
+///
+/// Each party member's share of `xp`, rounded down.
+///
+pub fn split(xp: usize, party: usize) -> usize {
+    xp.checked_div(party).unwrap_or(0)
+}
+
+///
+/// How a character earns new levels.
+///
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
+pub enum Advancement {
+    ///
+    /// Experience points earned so far.
+    ///
+    Experience(usize),
+    ///
+    /// Level granted by the DM, story beat by story beat.
+    ///
+    Milestone(usize),
+}
+
+impl Default for Advancement {
+    fn default() -> Self {
+        Self::Experience(0)
+    }
+}
+
+impl Advancement {
+    ///
+    /// Character level this is enough for.
+    ///
+    pub fn level(&self) -> usize {
+        match *self {
+            Self::Experience(xp) => level(xp),
+            Self::Milestone(level) => level.min(MAX_LEVEL),
+        }
+    }
+
+    ///
+    /// Experience points still needed for the next level,
+    /// or `None` if at milestones or already at the highest.
+    ///
+    pub fn to_next(&self) -> Option<usize> {
+        match *self {
+            Self::Experience(xp) => THRESHOLDS.get(level(xp)).map(|t| t - xp),
+            Self::Milestone(_) => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        ability::{
+            skills::{Athletics, Perception},
+            Strength,
+        },
+        class::{Champion, Choice, Fighter, LevelUp, LevelUpError},
+        creature::Creature,
+        identity::Identity,
+    };
+
+    use super::{level, proficiency_bonus, split, Advancement};
+
+    #[test]
+    fn thresholds() {
+        assert_eq!(level(0), 1);
+        assert_eq!(level(299), 1);
+        assert_eq!(level(300), 2);
+        assert_eq!(level(355_000), 20);
+        assert_eq!(level(usize::MAX), 20);
+
+        let bonuses = [1, 4, 5, 8, 9, 13, 17, 20].map(proficiency_bonus);
+        assert_eq!(bonuses, [2, 2, 3, 3, 4, 5, 6, 6]);
+
+        assert_eq!(split(1_100, 4), 275);
+        assert_eq!(split(1_100, 0), 0);
+
+        assert_eq!(Advancement::Experience(250).to_next(), Some(50));
+        assert_eq!(Advancement::Experience(400_000).to_next(), None);
+        assert_eq!(Advancement::Milestone(25).level(), 20);
+    }
+
+    #[test]
+    fn progression() {
+        let mut ent = Creature::default();
+        ent.stats().insert(Strength.id(), 15);
+        assert_eq!(ent.pending_levels(), 1);
+
+        ent.level_up(LevelUp::new(&Fighter).skills(&[Athletics.id(), Perception.id()]))
+            .unwrap();
+        assert_eq!(ent.gain_xp(7_000), 4);
+        assert_eq!(ent.milestone(), 4);
+
+        assert_eq!(ent.level_up(LevelUp::new(&Fighter)), Ok(2));
+        // Stuck on the subclass at 3rd level: still owed the rest.
+        assert_eq!(
+            ent.level_up(LevelUp::new(&Fighter)),
+            Err(LevelUpError::Missing(Choice::Subclass))
+        );
+        assert_eq!(ent.level(), 2);
+        assert_eq!(ent.proficency_modifier(), 2);
+
+        ent.set_advancement(Advancement::Milestone(ent.level()));
+        assert_eq!(ent.gain_xp(100_000), 0);
+        assert_eq!(ent.milestone(), 1);
+
+        ent.level_up(LevelUp::new(&Fighter).subclass(&Champion))
+            .unwrap();
+        assert_eq!(ent.pending_levels(), 0);
+
+        ent.set_advancement(Advancement::Experience(48_000));
+        for _ in 3..9 {
+            ent.add_level(&Fighter, &[]).unwrap();
+        }
+        assert_eq!(ent.proficency_modifier(), 4);
+    }
+}
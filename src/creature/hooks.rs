@@ -0,0 +1,195 @@
+use crate::dice::Rolls;
+
+use super::{context::CheckContext, effect::RollKind, Creature};
+
+///
+/// Runs before the d20 is rolled, e.g. to grant advantage.
+///
+pub type BeforeRollFn = dyn Fn(&Creature, RollKind, CheckContext) -> CheckContext;
+
+///
+/// Runs once the dice are down, but before the result is known:
+/// may add dice (Bardic Inspiration), or replace the d20 (Portent).
+///
+pub type AfterRollFn = dyn Fn(&Creature, RollKind, &CheckContext, Rolls) -> Rolls;
+
+///
+/// Runs once the roll is compared against its DC (or AC):
+/// may decide the result outright (Legendary Resistance).
+///
+pub type AfterResultFn = dyn Fn(&Creature, RollKind, &CheckContext, Outcome) -> Outcome;
+
+///
+/// Final result of a roll against a DC (or AC).
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Outcome {
+    pub total: i32,
+    pub dc: i32,
+    ///
+    /// Success or failure, whatever the total.
+    ///
+    pub forced: Option<bool>,
+}
+
+impl Outcome {
+    pub fn new(total: i32, dc: i32) -> Self {
+        Self {
+            total,
+            dc,
+            forced: None,
+        }
+    }
+
+    pub fn success(&self) -> bool {
+        self.forced.unwrap_or(self.total >= self.dc)
+    }
+}
+
+struct Listener<F: ?Sized> {
+    id: &'static str,
+    hook: Box<F>,
+}
+
+///
+/// Listeners on a creature's attacks, checks and saves,
+/// for feats and class features to hook into.
+///
+/// Listeners only get `&self`: any state (e.g. uses left)
+/// lives in the closure, behind a `Cell` or `RefCell`.
+///
+/// ***
+/// ```
+/// use xander::{
+///     ability::Strength,
+///     creature::{effect::RollKind, Creature},
+///     dice::{Roll, D20},
+///     identity::Identity,
+/// };
+///
+/// let mut ent = Creature::default();
+/// ent.stats().insert(Strength.id(), 10);
+///
+/// // Portent: the next d20 is a 1.
+/// ent.hooks().after_roll("5E::FEATURE::PORTENT", |_, _, _, rolls| {
+///     rolls.replace(&D20, [Roll::from(1)])
+/// });
+///
+/// assert_eq!(ent.save(Strength).total(), 1);
+/// ```
+///
+#[derive(Default)]
+pub struct Hooks {
+    before_roll: Vec<Listener<BeforeRollFn>>,
+    after_roll: Vec<Listener<AfterRollFn>>,
+    after_result: Vec<Listener<AfterResultFn>>,
+}
+
+impl Hooks {
+    pub fn before_roll(
+        &mut self,
+        id: &'static str,
+        hook: impl Fn(&Creature, RollKind, CheckContext) -> CheckContext + 'static,
+    ) -> &mut Self {
+        self.before_roll.push(Listener {
+            id,
+            hook: Box::new(hook),
+        });
+        self
+    }
+
+    pub fn after_roll(
+        &mut self,
+        id: &'static str,
+        hook: impl Fn(&Creature, RollKind, &CheckContext, Rolls) -> Rolls + 'static,
+    ) -> &mut Self {
+        self.after_roll.push(Listener {
+            id,
+            hook: Box::new(hook),
+        });
+        self
+    }
+
+    pub fn after_result(
+        &mut self,
+        id: &'static str,
+        hook: impl Fn(&Creature, RollKind, &CheckContext, Outcome) -> Outcome + 'static,
+    ) -> &mut Self {
+        self.after_result.push(Listener {
+            id,
+            hook: Box::new(hook),
+        });
+        self
+    }
+
+    ///
+    /// Unsubscribe every listener registered under `id`.
+    ///
+    pub fn remove(&mut self, id: &str) -> &mut Self {
+        self.before_roll.retain(|l| l.id != id);
+        self.after_roll.retain(|l| l.id != id);
+        self.after_result.retain(|l| l.id != id);
+        self
+    }
+
+    ///
+    /// Ids of every listener, in the order they run.
+    ///
+    pub fn ids(&self) -> Vec<&'static str> {
+        let ids = self
+            .before_roll
+            .iter()
+            .map(|l| l.id)
+            .chain(self.after_roll.iter().map(|l| l.id))
+            .chain(self.after_result.iter().map(|l| l.id));
+
+        let mut unique = vec![];
+        for id in ids {
+            if !unique.contains(&id) {
+                unique.push(id);
+            }
+        }
+        unique
+    }
+
+    pub(super) fn publish_before(
+        &self,
+        ent: &Creature,
+        kind: RollKind,
+        ctx: CheckContext,
+    ) -> CheckContext {
+        self.before_roll
+            .iter()
+            .fold(ctx, |ctx, l| (l.hook)(ent, kind, ctx))
+    }
+
+    pub(super) fn publish_roll(
+        &self,
+        ent: &Creature,
+        kind: RollKind,
+        ctx: &CheckContext,
+        rolls: Rolls,
+    ) -> Rolls {
+        self.after_roll
+            .iter()
+            .fold(rolls, |rolls, l| (l.hook)(ent, kind, ctx, rolls))
+    }
+
+    pub(super) fn publish_result(
+        &self,
+        ent: &Creature,
+        kind: RollKind,
+        ctx: &CheckContext,
+        outcome: Outcome,
+    ) -> Outcome {
+        self.after_result
+            .iter()
+            .fold(outcome, |outcome, l| (l.hook)(ent, kind, ctx, outcome))
+    }
+}
+
+impl std::fmt::Debug for Hooks {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Hooks({:?})", self.ids())
+    }
+}
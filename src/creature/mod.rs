@@ -1,4 +1,13 @@
+pub mod actions;
+pub mod concentration;
+pub mod context;
+pub mod death;
+pub mod effect;
+pub mod experience;
+pub mod hooks;
 pub mod proficiency;
+pub mod resource;
+pub mod rest;
 
 use std::{
     collections::HashMap,
@@ -6,65 +15,386 @@ use std::{
 };
 
 use crate::{
-    ability::{Ability, Check, Checks, Save, Saves},
-    dice::{Rolls, D20},
+    ability::{Ability, Check, Checks, Constitution, Dexterity, Save, CHECKS, SAVES},
+    class::{
+        self, Class, ClassError, ClassLevel, HitPoints, Improvement, LevelUp, LevelUpError,
+        Subclass,
+    },
+    damage::{DamageType, Defenses},
+    dice::{Rolls, D, D20},
+    feature::Feature,
     identity::Identity,
+    item::{tool::Tool, Tools},
+    origin::{self, Background, OriginError, Race, Senses, Size},
+    spell::{slots::Spellcasting, Spell},
 };
 
-use self::proficiency::{Proficiencies, Proficiency, ProficiencyType};
+use self::{
+    actions::Economy,
+    concentration::{Concentration, Event, Reason},
+    context::{CheckContext, Edge, Situational},
+    death::{DeathSave, DeathSaves},
+    effect::{Effect, RollKind},
+    experience::Advancement,
+    hooks::{Hooks, Outcome},
+    proficiency::{Grant, Proficiencies, Proficiency, ProficiencyType, Source},
+    resource::{Resource, ResourceError},
+    rest::{Recharge, Rest, RestError, RestKind, MAX_EXHAUSTION},
+};
+
+#[derive(Debug, Default)]
+pub struct Creature {
+    stats: HashMap<&'static str, usize>,
+    proficiencies: Proficiencies,
+    situational: Vec<Situational>,
+    edges: Vec<Edge>,
+    spellcasting: Spellcasting,
+    concentration: Option<Concentration>,
+    events: Vec<Event>,
+    speed: u32,
+    actions: Economy,
+    effects: Vec<Effect>,
+    hooks: Hooks,
+    features: Vec<Applied>,
+    classes: Vec<ClassLevel>,
+    race: Option<&'static dyn Race>,
+    background: Option<&'static dyn Background>,
+    size: Size,
+    senses: Senses,
+    hp_rolls: Vec<usize>,
+    damage: usize,
+    defenses: Defenses,
+    spells: Vec<&'static dyn Spell>,
+    advances: Vec<Advance>,
+    advancement: Advancement,
+    rest: Option<Rest>,
+    spent_hit_dice: HashMap<usize, usize>,
+    exhaustion: u8,
+    resources: Vec<Resource>,
+    proficiency: Option<i32>,
+    fixed_hp: Option<usize>,
+    fixed_ac: Option<i32>,
+    death_saves: DeathSaves,
+}
 
+///
+/// A feature, and the ability score
+/// increases it actually made (after capping).
+///
 #[derive(Debug)]
-pub struct Creature(HashMap<&'static str, usize>, Proficiencies);
+struct Applied {
+    feature: Box<dyn Feature>,
+    increases: Vec<(&'static str, usize)>,
+}
+
+///
+/// What a [Creature::level_up] changed,
+/// to be undone in reverse.
+///
+#[derive(Debug)]
+struct Advance {
+    class: &'static dyn Class,
+    features: usize,
+    increases: Vec<(&'static str, usize)>,
+    spells: usize,
+    subclass: bool,
+}
 
 #[allow(unused)]
 impl Creature {
-    fn proficency_modifier(&self) -> i32 {
-        2
+    ///
+    /// Proficiency bonus, going by total character level
+    /// unless set outright (e.g. by challenge rating).
+    ///
+    pub fn proficency_modifier(&self) -> i32 {
+        self.proficiency
+            .unwrap_or_else(|| experience::proficiency_bonus(self.level()))
     }
 
-    fn score<A>(&self, ability: impl Into<Box<A>>) -> Option<usize>
+    pub fn set_proficiency_bonus(&mut self, bonus: Option<i32>) -> &mut Self {
+        self.proficiency = bonus;
+        self
+    }
+
+    pub fn score<A>(&self, ability: impl Into<Box<A>>) -> Option<usize>
     where 
         A : Ability + ?Sized,
     {
         let ability: Box<A> = ability.into();
-        self.0.get(&ability.id()).copied()
+        self.stats.get(&ability.id()).copied()
+    }
+
+    ///
+    /// Ability score, by ability id.
+    ///
+    pub fn score_id(&self, ability: &str) -> Option<usize> {
+        self.stats.get(ability).copied()
     }
 
-    fn modifier<A>(&self, ability: impl Into<Box<A>>) -> Option<i32>
+    pub fn modifier<A>(&self, ability: impl Into<Box<A>>) -> Option<i32>
     where
         A : Ability + ?Sized,
     {
         self.score(ability).map(|a| (a as i32 - 10).div_floor(2))
     }
 
-    fn check<C>(&self, metric: C) -> Rolls 
+    ///
+    /// Ability modifier, by ability id.
+    ///
+    pub fn modifier_id(&self, ability: &str) -> Option<i32> {
+        self.score_id(ability).map(|a| (a as i32 - 10).div_floor(2))
+    }
+
+    ///
+    /// Flat bonus to a check or save (by proficiency category
+    /// and id) made with `ability`: its modifier plus any
+    /// proficiency, as a stat block would print it.
+    ///
+    pub fn bonus_id(&self, category: &str, id: &str, ability: &dyn Ability) -> i32 {
+        let ctx = CheckContext::default();
+        let prof = self
+            .proficiencies
+            .resolve_id_by(category, id, Some(ability), |g| self.bonus(g, &ctx))
+            .map(|g| self.bonus(g, &ctx))
+            .unwrap_or(0);
+
+        self.modifier_id(ability.id()).unwrap_or(0) + prof
+    }
+
+    ///
+    /// Proficiency bonus from a grant, in the given circumstances.
+    ///
+    fn bonus(&self, grant: &Grant, ctx: &CheckContext) -> i32 {
+        grant
+            .prof_type()
+            .bonus(self, ctx, self.proficency_modifier())
+    }
+
+    ///
+    /// Proficiency bonus to a roll in `ctx`, from whichever
+    /// grant (or fallback) gives the most in the circumstances.
+    ///
+    fn prof_bonus<I, P>(&self, prof: P, ability: &dyn Ability, ctx: &CheckContext) -> Option<i32>
+    where
+        I: Identity + Hash + Eq,
+        P: Proficiency<I>,
+    {
+        self.proficiencies
+            .resolve_by(prof, Some(ability), |g| self.bonus(g, ctx))
+            .map(|g| self.bonus(g, ctx))
+    }
+
+    ///
+    /// Mark `ctx` with any advantage or disadvantage
+    /// this creature has on the roll.
+    ///
+    fn apply_edges(&self, ctx: CheckContext) -> CheckContext {
+        self.edges.iter().fold(ctx, |ctx, e| e.apply(self, ctx))
+    }
+
+    ///
+    /// Apply every situational modifier relevant to `ctx`.
+    ///
+    fn apply_situational(&self, rolls: Rolls, ctx: &CheckContext) -> Rolls {
+        self.situational
+            .iter()
+            .filter_map(|s| s.modifier(self, ctx))
+            .fold(rolls, Rolls::modify)
+    }
+
+    ///
+    /// Apply every active effect on this kind of roll.
+    ///
+    fn apply_effects(&self, rolls: Rolls, kind: RollKind) -> Rolls {
+        self.effects
+            .iter()
+            .fold(rolls, |rolls, e| e.apply(rolls, kind))
+    }
+
+    ///
+    /// Get a roll's context ready: advantage from
+    /// [Edge]s, then any before-roll hooks.
+    ///
+    fn prepare(&self, kind: RollKind, ctx: CheckContext) -> CheckContext {
+        let ctx = match (kind, self.exhaustion) {
+            (RollKind::Check, 1..) | (_, 3..) => ctx.disadvantage(),
+            _ => ctx,
+        };
+
+        self.hooks.publish_before(self, kind, self.apply_edges(ctx))
+    }
+
+    ///
+    /// Finish a roll: situational modifiers,
+    /// effects, then any after-roll hooks.
+    ///
+    fn finish(&self, kind: RollKind, ctx: &CheckContext, rolls: Rolls) -> Rolls {
+        let rolls = self.apply_effects(self.apply_situational(rolls, ctx), kind);
+        self.hooks.publish_roll(self, kind, ctx, rolls)
+    }
+
+    ///
+    /// Compare a finished roll against its DC (or AC),
+    /// and let the after-result hooks have their say.
+    ///
+    pub fn resolve(&self, kind: RollKind, ctx: &CheckContext, rolls: Rolls, dc: i32) -> Outcome {
+        let outcome = Outcome::new(rolls.total(), dc);
+        self.hooks.publish_result(self, kind, ctx, outcome)
+    }
+
+    pub fn check<C>(&self, metric: C) -> Rolls 
     where 
         C :  Check + Hash + Eq,
     {
-        let mut r = D20()
+        self.check_in(metric, CheckContext::default())
+    }
+
+    ///
+    /// Ability check, made in some particular circumstances.
+    ///
+    pub fn check_in<C>(&self, metric: C, ctx: CheckContext) -> Rolls
+    where
+        C: Check + Hash + Eq,
+    {
+        self.roll_check(metric, ctx).0
+    }
+
+    ///
+    /// Ability check against a DC.
+    ///
+    pub fn check_against<C>(&self, metric: C, ctx: CheckContext, dc: i32) -> Outcome
+    where
+        C: Check + Hash + Eq,
+    {
+        let (rolls, ctx) = self.roll_check(metric, ctx);
+        self.resolve(RollKind::Check, &ctx, rolls, dc)
+    }
+
+    fn roll_check<C>(&self, metric: C, ctx: CheckContext) -> (Rolls, CheckContext)
+    where
+        C: Check + Hash + Eq,
+    {
+        let base = C::base();
+        let ctx = self.prepare(RollKind::Check, ctx.making(&metric, base.as_ref()));
+
+        let mut r = ctx.d20()
             + self
                 .modifier::<dyn Ability>(C::base())
-                .unwrap();
+                .unwrap_or(0);
 
-        if let Some(prof) = self
-            .proficient(Checks(metric))
-            .map(|t| t.bonus(self, self.proficency_modifier()))
-        {
+        if let Some(prof) = self.prof_bonus(Checks(metric), base.as_ref(), &ctx) {
             r = r + prof;
         }
         
-        r
+        (self.finish(RollKind::Check, &ctx, r), ctx)
     }
 
-    fn save<S>(&self, metric: S) -> Rolls 
+    ///
+    /// Ability check made using a tool.
+    ///
+    /// Proficiency in either the tool or the check applies,
+    /// and proficiency in both grants advantage (Xanathar's Guide).
+    /// Fallbacks count towards the bonus, but not the advantage.
+    ///
+    pub fn tool_check<T, C>(&self, tool: T, metric: C) -> Rolls
+    where
+        T: Tool + Clone + Hash + Eq,
+        C: Check + Clone + Hash + Eq,
+    {
+        let base = C::base();
+        let mut ctx = CheckContext::default()
+            .using(tool.clone())
+            .making(&metric, base.as_ref());
+
+        if self.proficient(Tools(tool.clone())).is_some()
+            && self.proficient(Checks(metric.clone())).is_some()
+        {
+            ctx = ctx.advantage();
+        }
+        let ctx = self.prepare(RollKind::Check, ctx);
+
+        let tool_prof = self.prof_bonus(Tools(tool), base.as_ref(), &ctx);
+        let check_prof = self.prof_bonus(Checks(metric), base.as_ref(), &ctx);
+
+        let r = ctx.d20()
+            + self.modifier::<dyn Ability>(C::base()).unwrap_or(0)
+            + tool_prof.max(check_prof).unwrap_or(0);
+
+        self.finish(RollKind::Check, &ctx, r)
+    }
+
+    pub fn save<S>(&self, metric: S) -> Rolls 
     where 
         S : Save + Ability + Hash + Eq,
     {
-        D20()
-            + self
-                .proficient(Saves(metric))
-                .map(|t| t.bonus(self, self.proficency_modifier()))
-                .unwrap_or(0)
+        self.save_in(metric, CheckContext::default())
+    }
+
+    ///
+    /// Saving throw, made in some particular circumstances.
+    ///
+    pub fn save_in<S>(&self, metric: S, ctx: CheckContext) -> Rolls
+    where
+        S: Save + Ability + Hash + Eq,
+    {
+        self.roll_save(&metric, ctx).0
+    }
+
+    ///
+    /// Saving throw against a DC.
+    ///
+    pub fn save_against<S>(&self, metric: S, ctx: CheckContext, dc: i32) -> Outcome
+    where
+        S: Save + Ability + Hash + Eq,
+    {
+        self.save_against_dyn(&metric, ctx, dc)
+    }
+
+    ///
+    /// Saving throw against a DC, with an ability
+    /// only known at runtime (e.g. a spell's).
+    ///
+    pub fn save_against_dyn(&self, ability: &dyn Ability, ctx: CheckContext, dc: i32) -> Outcome {
+        let (rolls, ctx) = self.roll_save(ability, ctx);
+        self.resolve(RollKind::Save, &ctx, rolls, dc)
+    }
+
+    fn roll_save(&self, ability: &dyn Ability, ctx: CheckContext) -> (Rolls, CheckContext) {
+        let ctx = self.prepare(RollKind::Save, ctx.making(ability, ability));
+
+        let prof = self
+            .proficiencies
+            .resolve_id_by(SAVES, ability.id(), Some(ability), |g| self.bonus(g, &ctx))
+            .map(|g| self.bonus(g, &ctx));
+        let r = ctx.d20() + self.modifier_id(ability.id()).unwrap_or(0) + prof.unwrap_or(0);
+
+        (self.finish(RollKind::Save, &ctx, r), ctx)
+    }
+
+    ///
+    /// Attack roll, with a total `bonus` to hit.
+    ///
+    pub fn attack(&self, bonus: i32, ctx: CheckContext) -> Rolls {
+        self.roll_attack(bonus, ctx).0
+    }
+
+    ///
+    /// Attack roll against an armor class.
+    ///
+    pub fn attack_against(&self, bonus: i32, ctx: CheckContext, ac: i32) -> Outcome {
+        let (rolls, ctx) = self.roll_attack(bonus, ctx);
+        self.resolve(RollKind::Attack, &ctx, rolls, ac)
+    }
+
+    ///
+    /// Attack roll, along with the context it was made in
+    /// (for [Creature::resolve]).
+    ///
+    pub(crate) fn roll_attack(&self, bonus: i32, ctx: CheckContext) -> (Rolls, CheckContext) {
+        let ctx = self.prepare(RollKind::Attack, ctx);
+        let r = ctx.d20() + bonus;
+
+        (self.finish(RollKind::Attack, &ctx, r), ctx)
     }
 
     pub fn proficient<I, P>(&self, prof: P) -> Option<&dyn ProficiencyType>
@@ -72,36 +402,1102 @@ impl Creature {
         I: Identity + Hash + Eq,
         P: Proficiency<I>,
     {
-        self.1.has(prof)
+        self.proficiencies.has(prof)
+    }
+
+    ///
+    /// Why is this creature proficient?
+    ///
+    /// Lists every grant, the one that applies first.
+    ///
+    pub fn explain<I, P>(&self, prof: P) -> Vec<&Grant>
+    where
+        I: Identity + Hash + Eq,
+        P: Proficiency<I>,
+    {
+        self.proficiencies.explain(prof)
+    }
+
+    ///
+    /// Same as [Self::explain], by category and id
+    /// (e.g. from a [CheckContext]).
+    ///
+    pub fn explain_id(&self, category: &str, id: &str) -> Vec<&Grant> {
+        self.proficiencies.explain_id(category, id)
+    }
+
+    ///
+    /// The grant that applies, fallbacks included,
+    /// by category and id.
+    ///
+    pub fn resolve_id(
+        &self,
+        category: &str,
+        id: &str,
+        ability: Option<&dyn Ability>,
+    ) -> Option<&Grant> {
+        self.proficiencies.resolve_id(category, id, ability)
+    }
+
+    ///
+    /// Modifier of a class's spellcasting ability.
+    ///
+    fn spellcasting_modifier(&self, class: &str) -> Option<i32> {
+        let caster = self.spellcasting.caster(class)?;
+        self.stats
+            .get(caster.ability().id())
+            .map(|score| (*score as i32 - 10).div_floor(2))
+    }
+
+    ///
+    /// Spell save DC of a class: 8 + proficiency + modifier.
+    ///
+    pub fn spell_save_dc(&self, class: &str) -> Option<i32> {
+        self.spellcasting_modifier(class)
+            .map(|m| 8 + self.proficency_modifier() + m)
+    }
+
+    ///
+    /// Spell attack bonus of a class: proficiency + modifier.
+    ///
+    pub fn spell_attack_bonus(&self, class: &str) -> Option<i32> {
+        self.spellcasting_modifier(class)
+            .map(|m| self.proficency_modifier() + m)
+    }
+
+    ///
+    /// Roll a spell attack with a class's spellcasting.
+    ///
+    pub fn spell_attack(&self, class: &str) -> Option<Rolls> {
+        self.spell_attack_bonus(class)
+            .map(|bonus| self.attack(bonus, CheckContext::default()))
+    }
+
+    pub fn spellcasting(&mut self) -> &mut Spellcasting {
+        &mut self.spellcasting
+    }
+
+    ///
+    /// Start concentrating on a spell,
+    /// ending concentration on any other.
+    ///
+    /// Returns `false` (and changes nothing) if
+    /// the spell doesn't require concentration.
+    ///
+    pub fn concentrate(&mut self, spell: &dyn Spell) -> bool {
+        if !spell.concentration() {
+            return false;
+        }
+
+        self.end_concentration(Reason::Replaced(spell.id()));
+        self.concentration = Some(Concentration::new(spell));
+        self.events.push(Event::Started(spell.id()));
+        true
+    }
+
+    pub fn concentration(&self) -> Option<&Concentration> {
+        self.concentration.as_ref()
+    }
+
+    ///
+    /// Stop concentrating, for the given reason.
+    ///
+    pub fn end_concentration(&mut self, reason: Reason) {
+        if let Some(c) = self.concentration.take() {
+            self.events.push(Event::Ended(c.spell(), reason));
+        }
+    }
+
+    ///
+    /// Take damage, rolling a CON save to
+    /// keep concentrating if need be.
+    ///
+    /// Damage at 0 hit points is a failed death save,
+    /// and damage left over (on dropping to 0) of at
+    /// least the hit point maximum kills outright.
+    /// Dropping to 0 ends concentration, with no save.
+    ///
+    pub fn take_damage(&mut self, amount: usize) {
+        if amount == 0 {
+            return;
+        }
+
+        let (hp, max) = (self.hp(), self.max_hp());
+        match amount.saturating_sub(hp) {
+            _ if max == 0 => {}
+            left if left >= max => self.death_saves.die(),
+            _ if hp == 0 => self.death_saves.fail(1),
+            _ => {}
+        }
+
+        self.damage = (self.damage + amount).min(max);
+
+        if max > 0 && self.hp() == 0 {
+            self.incapacitate();
+            return;
+        }
+
+        let Some(c) = self.concentration else {
+            return;
+        };
+
+        let ctx = CheckContext::default().tag(concentration::TAG);
+        let outcome = self.save_against(Constitution, ctx, concentration::dc(amount));
+        let Outcome { total: roll, dc, .. } = outcome;
+
+        match outcome.success() {
+            true => self.events.push(Event::Maintained {
+                spell: c.spell(),
+                roll,
+                dc,
+            }),
+            false => self.end_concentration(Reason::FailedSave { roll, dc }),
+        }
+    }
+
+    ///
+    /// Take damage of a type, after resistances,
+    /// immunities and vulnerabilities.
+    ///
+    /// Returns the damage actually taken.
+    ///
+    pub fn take_damage_of(&mut self, amount: usize, kind: DamageType) -> usize {
+        let amount = self.defenses.damage_taken(amount, kind);
+        self.take_damage(amount);
+        amount
+    }
+
+    pub fn defenses(&self) -> &Defenses {
+        &self.defenses
+    }
+
+    pub fn defenses_mut(&mut self) -> &mut Defenses {
+        &mut self.defenses
+    }
+
+    ///
+    /// Being incapacitated ends concentration.
+    ///
+    pub fn incapacitate(&mut self) {
+        self.end_concentration(Reason::Incapacitated);
     }
 
-    fn proficiencies(&mut self) -> &mut Proficiencies {
-        &mut self.1
+    ///
+    /// Take every event emitted since the last call.
+    ///
+    pub fn events(&mut self) -> Vec<Event> {
+        std::mem::take(&mut self.events)
     }
 
-    fn stats(&mut self) -> &mut HashMap<&'static str, usize> {
-        &mut self.0
+    ///
+    /// Walking speed in feet, after exhaustion.
+    ///
+    pub fn speed(&self) -> u32 {
+        match self.exhaustion {
+            0..=1 => self.speed,
+            2..=4 => self.speed / 2,
+            _ => 0,
+        }
+    }
+
+    pub fn set_speed(&mut self, speed: u32) -> &mut Self {
+        self.speed = speed;
+        self
+    }
+
+    ///
+    /// What's left to spend this turn.
+    ///
+    pub fn actions(&mut self) -> &mut Economy {
+        &mut self.actions
+    }
+
+    ///
+    /// Start of this creature's turn.
+    ///
+    pub fn start_turn(&mut self) {
+        self.actions.start_turn(self.speed());
+        self.resources.iter_mut().for_each(|r| match r.recharge() {
+            Recharge::Turn => r.restore(),
+            _ => {
+                r.roll_recharge();
+            }
+        });
+    }
+
+    ///
+    /// End of this creature's turn.
+    ///
+    pub fn end_turn(&mut self) {
+        self.actions.end_turn();
+        self.effects.iter_mut().for_each(Effect::tick);
+        self.effects.retain(|e| !e.expired());
+    }
+
+    ///
+    /// Put an effect on this creature.
+    ///
+    /// Effects of the same name don't stack:
+    /// the newer one replaces the older.
+    ///
+    pub fn add_effect(&mut self, effect: Effect) -> &mut Self {
+        self.effects.retain(|e| e.id() != effect.id());
+        self.effects.push(effect);
+        self
+    }
+
+    ///
+    /// Effects still running.
+    ///
+    pub fn effects(&self) -> impl Iterator<Item = &Effect> {
+        self.effects.iter().filter(|e| !e.expired())
+    }
+
+    ///
+    /// End every effect from `source`
+    /// (e.g. when concentration is broken).
+    ///
+    pub fn remove_effects(&mut self, source: &str) -> &mut Self {
+        self.effects.retain(|e| e.source() != source);
+        self
+    }
+
+    ///
+    /// Add a modifier that applies to some
+    /// checks and saves, depending on their context.
+    ///
+    pub fn add_situational(&mut self, situational: Situational) -> &mut Self {
+        self.situational.push(situational);
+        self
+    }
+
+    ///
+    /// Add a source of advantage or disadvantage
+    /// on some checks and saves.
+    ///
+    pub fn add_edge(&mut self, edge: Edge) -> &mut Self {
+        self.edges.push(edge);
+        self
+    }
+
+    ///
+    /// Listeners on this creature's rolls.
+    ///
+    pub fn hooks(&mut self) -> &mut Hooks {
+        &mut self.hooks
+    }
+
+    ///
+    /// Apply a feat or feature onto this creature.
+    ///
+    pub fn add_feature(&mut self, feature: impl Feature + 'static) -> &mut Self {
+        self.add_feature_boxed(Box::new(feature))
+    }
+
+    pub fn add_feature_boxed(&mut self, feature: Box<dyn Feature>) -> &mut Self {
+        let increases = self.increase(feature.ability_scores());
+
+        feature.grant(self);
+        self.features.push(Applied { feature, increases });
+        self
+    }
+
+    ///
+    /// Raise ability scores (that are set) by as much as
+    /// they can go under 20, returning the actual increases.
+    ///
+    fn increase(&mut self, scores: Vec<(&'static str, usize)>) -> Vec<(&'static str, usize)> {
+        scores
+            .into_iter()
+            .filter_map(|(ability, increase)| {
+                let score = self.stats.get_mut(ability)?;
+                let increase = increase.min(20usize.saturating_sub(*score));
+                *score += increase;
+                Some((ability, increase))
+            })
+            .collect()
+    }
+
+    ///
+    /// Undo everything a feature did.
+    ///
+    pub fn remove_feature(&mut self, id: &str) -> &mut Self {
+        let (removed, kept) = std::mem::take(&mut self.features)
+            .into_iter()
+            .partition::<Vec<_>, _>(|a| a.feature.id() == id);
+        self.features = kept;
+
+        removed.into_iter().for_each(|a| self.revert(a));
+        self
+    }
+
+    fn revert(&mut self, Applied { feature, increases }: Applied) {
+        self.decrease(increases);
+        self.proficiencies.remove(feature.source());
+
+        let id = feature.id();
+        self.situational.retain(|s| s.id() != id);
+        self.edges.retain(|e| e.id() != id);
+        self.hooks.remove(id);
+        self.resources.retain(|r| r.id() != id);
+        self.remove_effects(id);
+    }
+
+    fn decrease(&mut self, increases: Vec<(&'static str, usize)>) {
+        for (ability, increase) in increases {
+            if let Some(score) = self.stats.get_mut(ability) {
+                *score -= increase.min(*score);
+            }
+        }
+    }
+
+    pub fn features(&self) -> impl Iterator<Item = &dyn Feature> {
+        self.features.iter().map(|a| a.feature.as_ref())
+    }
+
+    ///
+    /// Total character level, across all classes.
+    ///
+    pub fn level(&self) -> usize {
+        self.classes.iter().map(ClassLevel::level).sum()
+    }
+
+    ///
+    /// Levels taken in a class, by id.
+    ///
+    pub fn class_level(&self, class: &str) -> usize {
+        self.classes
+            .iter()
+            .find(|c| c.class().id() == class)
+            .map_or(0, ClassLevel::level)
+    }
+
+    ///
+    /// Every class taken, in the order they were.
+    ///
+    pub fn classes(&self) -> &[ClassLevel] {
+        &self.classes
+    }
+
+    ///
+    /// Take a level in `class`, picking `skills` (by id) if
+    /// it's the first level in that class and it offers any.
+    ///
+    /// Returns the new level in that class.
+    ///
+    pub fn add_level(
+        &mut self,
+        class: &'static dyn Class,
+        skills: &[&'static str],
+    ) -> Result<usize, ClassError> {
+        if self.level() >= class::MAX_LEVEL {
+            return Err(ClassError::MaxLevel);
+        }
+
+        let existing = self
+            .classes
+            .iter()
+            .position(|c| c.class().id() == class.id());
+        let first = self.classes.is_empty();
+
+        // Multiclassing: prerequisites of the old classes and the new one.
+        if !first && existing.is_none() {
+            let score = |ability: &str| self.stats.get(ability).copied().unwrap_or(0);
+            if let Some(unmet) = self
+                .classes
+                .iter()
+                .map(ClassLevel::class)
+                .chain([class])
+                .find(|c| !class::meets(*c, score))
+            {
+                return Err(ClassError::Prerequisite(unmet.id()));
+            }
+        }
+
+        let (count, offers) = class.skills();
+        let count = match (first, existing) {
+            (true, _) => count,
+            (false, None) => class.multiclass_skills(),
+            (false, Some(_)) => 0,
+        };
+        let picked = class::pick(&offers, count, skills)?;
+
+        let source = Source::Class(class.id());
+        let granted = match (first, existing) {
+            (true, _) => class
+                .saves()
+                .into_iter()
+                .chain(class.proficiencies())
+                .collect(),
+            (false, None) => class.multiclass_proficiencies(),
+            (false, Some(_)) => vec![],
+        };
+        granted
+            .iter()
+            .chain(picked)
+            .for_each(|offer| offer.grant(&mut self.proficiencies, source));
+
+        let entry = match existing {
+            Some(i) => &mut self.classes[i],
+            None => {
+                self.classes.push(ClassLevel::new(class));
+                self.classes.last_mut().unwrap()
+            }
+        };
+        let level = entry.level() + 1;
+        entry.set_level(level);
+        let subclass = entry.subclass();
+
+        // Most the hit die allows at first level, the average after.
+        let die = class.hit_die();
+        self.hp_rolls.push(match first {
+            true => die,
+            false => die / 2 + 1,
+        });
+
+        class
+            .features(level)
+            .into_iter()
+            .chain(subclass.map(|s| s.features(level)).unwrap_or_default())
+            .for_each(|f| {
+                self.add_feature_boxed(f);
+            });
+
+        if let Some(caster) = class.caster(level) {
+            self.spellcasting.add(caster);
+        }
+
+        Ok(level)
+    }
+
+    ///
+    /// Choose a subclass, gaining its features
+    /// for every level already taken.
+    ///
+    pub fn set_subclass(&mut self, subclass: &'static dyn Subclass) -> Result<(), ClassError> {
+        let entry = self
+            .classes
+            .iter_mut()
+            .find(|c| c.class().id() == subclass.class())
+            .ok_or(ClassError::MissingClass(subclass.class()))?;
+
+        if let Some(taken) = entry.subclass() {
+            return Err(ClassError::SubclassTaken(taken.id()));
+        }
+
+        let required = entry.class().subclass_level();
+        if entry.level() < required {
+            return Err(ClassError::SubclassLevel { required });
+        }
+
+        entry.set_subclass(Some(subclass));
+        let level = entry.level();
+
+        (1..=level)
+            .flat_map(|l| subclass.features(l))
+            .for_each(|f| {
+                self.add_feature_boxed(f);
+            });
+
+        Ok(())
+    }
+
+    ///
+    /// Apply a race: ability scores, speed, size,
+    /// darkvision, proficiencies and traits, picking
+    /// a replacement (by id) for each of its skills
+    /// this creature already has.
+    ///
+    /// Nothing is applied unless every replacement is valid.
+    ///
+    pub fn set_race(
+        &mut self,
+        race: &'static dyn Race,
+        replacements: &[&'static str],
+    ) -> Result<(), OriginError> {
+        if let Some(race) = self.race {
+            return Err(OriginError::AlreadySet(race.id()));
+        }
+
+        let offers = origin::resolve(race.proficiencies(), replacements, |id| {
+            !self.explain_id(CHECKS, id).is_empty()
+        })?;
+
+        self.increase(race.ability_scores());
+        self.speed = race.speed();
+        self.size = race.size();
+        self.senses.darkvision = race.darkvision();
+
+        let source = Source::Race(race.id());
+        offers
+            .iter()
+            .for_each(|offer| offer.grant(&mut self.proficiencies, source));
+        race.traits().into_iter().for_each(|t| {
+            self.add_feature_boxed(t);
+        });
+
+        self.race = Some(race);
+        Ok(())
+    }
+
+    ///
+    /// Apply a background, picking a replacement (by id)
+    /// for each of its skills this creature already has.
+    ///
+    /// Nothing is applied unless every replacement is valid.
+    ///
+    pub fn set_background(
+        &mut self,
+        background: &'static dyn Background,
+        replacements: &[&'static str],
+    ) -> Result<(), OriginError> {
+        if let Some(background) = self.background {
+            return Err(OriginError::AlreadySet(background.id()));
+        }
+
+        let offers = origin::resolve(background.proficiencies(), replacements, |id| {
+            !self.explain_id(CHECKS, id).is_empty()
+        })?;
+
+        let source = Source::Background(background.id());
+        offers
+            .iter()
+            .for_each(|offer| offer.grant(&mut self.proficiencies, source));
+        background.traits().into_iter().for_each(|t| {
+            self.add_feature_boxed(t);
+        });
+
+        self.background = Some(background);
+        Ok(())
+    }
+
+    pub fn race(&self) -> Option<&'static dyn Race> {
+        self.race
+    }
+
+    pub fn background(&self) -> Option<&'static dyn Background> {
+        self.background
+    }
+
+    pub fn size(&self) -> Size {
+        self.size
+    }
+
+    pub fn set_size(&mut self, size: Size) -> &mut Self {
+        self.size = size;
+        self
+    }
+
+    pub fn senses(&self) -> Senses {
+        self.senses
+    }
+
+    pub fn set_senses(&mut self, senses: Senses) -> &mut Self {
+        self.senses = senses;
+        self
+    }
+
+    ///
+    /// Take a level, with every choice it asks for:
+    /// nothing is applied unless they all check out.
+    ///
+    /// Returns the new level in that class.
+    ///
+    pub fn level_up(&mut self, level_up: LevelUp) -> Result<usize, LevelUpError> {
+        level_up.validate(self)?;
+
+        let LevelUp {
+            class,
+            hit_points,
+            skills,
+            improvement,
+            subclass,
+            spells,
+        } = level_up;
+
+        let features = self.features.len();
+        let known = self.spells.len();
+        let level = self.add_level(class, &skills)?;
+
+        let first = self.level() == 1;
+        let last = self.hp_rolls.last_mut();
+        if let (HitPoints::Roll, false, Some(hp)) = (hit_points, first, last) {
+            *hp = D(class.hit_die())(1).total() as usize;
+        }
+
+        let increases = match improvement {
+            Some(Improvement::Scores(scores)) => self.increase(scores),
+            Some(Improvement::Feat(feat)) => {
+                self.add_feature_boxed(feat);
+                vec![]
+            }
+            None => vec![],
+        };
+
+        if let Some(subclass) = subclass {
+            self.set_subclass(subclass)?;
+        }
+
+        self.spells.extend(spells);
+        self.advances.push(Advance {
+            class,
+            features,
+            increases,
+            spells: known,
+            subclass: subclass.is_some(),
+        });
+
+        Ok(level)
+    }
+
+    ///
+    /// Undo the last [Creature::level_up], returning
+    /// the class the level was taken in.
+    ///
+    pub fn undo_level_up(&mut self) -> Option<&'static dyn Class> {
+        let Advance {
+            class,
+            features,
+            increases,
+            spells,
+            subclass,
+        } = self.advances.pop()?;
+
+        let added = self.features.split_off(features.min(self.features.len()));
+        added.into_iter().rev().for_each(|a| self.revert(a));
+        self.decrease(increases);
+        self.spells.truncate(spells);
+        self.hp_rolls.pop();
+
+        let i = self
+            .classes
+            .iter()
+            .position(|c| c.class().id() == class.id())?;
+        let entry = &mut self.classes[i];
+        let level = entry.level() - 1;
+        entry.set_level(level);
+        if subclass {
+            entry.set_subclass(None);
+        }
+
+        match (level, class.caster(level)) {
+            (0, _) => {
+                self.classes.remove(i);
+                self.proficiencies.remove(Source::Class(class.id()));
+                self.spellcasting.remove(class.id());
+            }
+            (_, Some(caster)) => {
+                self.spellcasting.add(caster);
+            }
+            (_, None) => {
+                self.spellcasting.remove(class.id());
+            }
+        }
+
+        Some(class)
+    }
+
+    pub fn advancement(&self) -> Advancement {
+        self.advancement
+    }
+
+    ///
+    /// Switch between experience points and milestones,
+    /// e.g. `Advancement::Milestone(ent.level())`.
+    ///
+    pub fn set_advancement(&mut self, advancement: Advancement) -> &mut Self {
+        self.advancement = advancement;
+        self
+    }
+
+    ///
+    /// Earn experience points (ignored at milestones),
+    /// returning how many levels are now there to take.
+    ///
+    pub fn gain_xp(&mut self, xp: usize) -> usize {
+        if let Advancement::Experience(total) = &mut self.advancement {
+            *total = total.saturating_add(xp);
+        }
+        self.pending_levels()
+    }
+
+    ///
+    /// Reach a milestone (ignored when earning experience),
+    /// returning how many levels are now there to take.
+    ///
+    pub fn milestone(&mut self) -> usize {
+        if let Advancement::Milestone(level) = &mut self.advancement {
+            *level = (*level + 1).min(class::MAX_LEVEL);
+        }
+        self.pending_levels()
+    }
+
+    ///
+    /// Levels earned, but not yet taken with [Creature::level_up].
+    ///
+    pub fn pending_levels(&self) -> usize {
+        self.advancement.level().saturating_sub(self.level())
+    }
+
+    ///
+    /// Spells learned on leveling up.
+    ///
+    pub fn spells(&self) -> &[&'static dyn Spell] {
+        &self.spells
+    }
+
+    ///
+    /// Hit dice rolled (or averaged), one per level,
+    /// without the Constitution modifier.
+    ///
+    pub fn hp_rolls(&self) -> &[usize] {
+        &self.hp_rolls
+    }
+
+    ///
+    /// Hit point maximum: at least 1 per level, with
+    /// the current Constitution modifier applied to every level
+    /// (unless set with [Creature::set_max_hp]).
+    ///
+    /// Halved from the 4th level of exhaustion.
+    ///
+    pub fn max_hp(&self) -> usize {
+        let con = self.modifier(Constitution).unwrap_or(0);
+        let max = self.fixed_hp.unwrap_or_else(|| {
+            self.hp_rolls
+                .iter()
+                .map(|die| (*die as i32 + con).max(1) as usize)
+                .sum()
+        });
+
+        match self.exhaustion {
+            4.. => max / 2,
+            _ => max,
+        }
+    }
+
+    ///
+    /// Set the hit point maximum outright (e.g. as printed
+    /// in a stat block), instead of working it out from levels.
+    ///
+    pub fn set_max_hp(&mut self, hp: Option<usize>) -> &mut Self {
+        self.fixed_hp = hp;
+        self
+    }
+
+    ///
+    /// Armor class: 10 plus the DEX modifier,
+    /// unless set outright (e.g. by a stat block).
+    ///
+    pub fn armor_class(&self) -> i32 {
+        self.fixed_ac
+            .unwrap_or_else(|| 10 + self.modifier(Dexterity).unwrap_or(0))
+    }
+
+    pub fn set_armor_class(&mut self, ac: Option<i32>) -> &mut Self {
+        self.fixed_ac = ac;
+        self
+    }
+
+    pub fn hp(&self) -> usize {
+        self.max_hp().saturating_sub(self.damage)
+    }
+
+    ///
+    /// Regain hit points, up to the maximum.
+    ///
+    /// The dead regain nothing.
+    ///
+    pub fn heal(&mut self, amount: usize) -> &mut Self {
+        if self.dead() {
+            return self;
+        }
+
+        self.damage = self.damage.saturating_sub(amount);
+        if amount > 0 {
+            self.death_saves = DeathSaves::default();
+        }
+        self
+    }
+
+    pub fn death_saves(&self) -> DeathSaves {
+        self.death_saves
+    }
+
+    ///
+    /// At 0 hit points, and neither stable nor dead.
+    ///
+    pub fn dying(&self) -> bool {
+        self.hp() == 0 && !self.death_saves.stable() && !self.death_saves.dead()
+    }
+
+    pub fn dead(&self) -> bool {
+        self.death_saves.dead()
+    }
+
+    ///
+    /// Roll a death saving throw, if dying.
+    ///
+    pub fn death_save(&mut self) -> Option<DeathSave> {
+        if !self.dying() {
+            return None;
+        }
+
+        let outcome = match D20().total() {
+            20 => {
+                self.heal(1);
+                return Some(DeathSave::Revived);
+            }
+            1 => {
+                self.death_saves.fail(2);
+                DeathSave::Failure(2)
+            }
+            roll if roll >= death::DC => {
+                self.death_saves.succeed();
+                DeathSave::Success
+            }
+            _ => {
+                self.death_saves.fail(1);
+                DeathSave::Failure(1)
+            }
+        };
+
+        Some(match self.death_saves {
+            saves if saves.dead() => DeathSave::Dead,
+            saves if saves.stable() => DeathSave::Stable,
+            _ => outcome,
+        })
+    }
+
+    ///
+    /// Hit dice left to spend, as `(sides, left)`, biggest first.
+    ///
+    pub fn hit_dice(&self) -> Vec<(usize, usize)> {
+        let mut dice: Vec<(usize, usize)> = vec![];
+        for entry in &self.classes {
+            let die = entry.class().hit_die();
+            match dice.iter_mut().find(|(sides, _)| *sides == die) {
+                Some((_, total)) => *total += entry.level(),
+                None => dice.push((die, entry.level())),
+            }
+        }
+
+        dice.sort_by_key(|(die, _)| std::cmp::Reverse(*die));
+        dice.into_iter()
+            .map(|(die, total)| {
+                let spent = self.spent_hit_dice.get(&die).copied().unwrap_or(0);
+                (die, total.saturating_sub(spent))
+            })
+            .collect()
+    }
+
+    ///
+    /// Begin a rest, to be [Creature::finish_rest]ed.
+    ///
+    pub fn start_rest(&mut self, kind: RestKind) -> Result<(), RestError> {
+        if let Some(rest) = self.rest {
+            return Err(RestError::Resting(rest.kind()));
+        }
+
+        if kind == RestKind::Long && self.max_hp() > 0 && self.hp() == 0 {
+            return Err(RestError::Unconscious);
+        }
+
+        self.rest = Some(Rest::new(kind));
+        Ok(())
+    }
+
+    pub fn rest(&self) -> Option<Rest> {
+        self.rest
+    }
+
+    ///
+    /// Strenuous activity (fighting, casting, walking)
+    /// in the middle of a rest, in minutes.
+    ///
+    pub fn exert(&mut self, minutes: u32) -> &mut Self {
+        if let Some(rest) = &mut self.rest {
+            rest.exert(minutes);
+        }
+        self
+    }
+
+    ///
+    /// Spend a hit die during a short rest, regaining
+    /// its roll plus the Constitution modifier in hit points.
+    ///
+    /// Returns the hit points regained.
+    ///
+    pub fn spend_hit_die(&mut self, die: usize) -> Result<usize, RestError> {
+        match self.rest.map(|r| r.kind()) {
+            Some(RestKind::Short) => {}
+            _ => return Err(RestError::NotShortRest),
+        }
+
+        match self.hit_dice().iter().find(|(sides, _)| *sides == die) {
+            Some((_, 1..)) => {}
+            _ => return Err(RestError::NoHitDice(die)),
+        }
+
+        *self.spent_hit_dice.entry(die).or_default() += 1;
+
+        let con = self.modifier(Constitution).unwrap_or(0);
+        let healed = (D(die)(1) + con).total().max(0) as usize;
+        self.heal(healed);
+
+        Ok(healed)
+    }
+
+    ///
+    /// End the current rest, gaining its benefits
+    /// unless it was interrupted.
+    ///
+    /// A short rest brings back Pact Magic slots and short rest
+    /// features. A long rest brings back everything: hit points,
+    /// spell slots and features, half of all hit dice
+    /// (at least one), and takes away a level of exhaustion.
+    ///
+    pub fn finish_rest(&mut self) -> Result<RestKind, RestError> {
+        let rest = self.rest.take().ok_or(RestError::NotResting)?;
+        let kind = rest.kind();
+
+        if rest.interrupted() {
+            return Err(RestError::Interrupted(kind));
+        }
+
+        match kind {
+            RestKind::Short => {
+                self.spellcasting.short_rest();
+            }
+            RestKind::Long => {
+                self.exhaustion = self.exhaustion.saturating_sub(1);
+                self.damage = 0;
+                self.spellcasting.long_rest();
+
+                let mut regained = (self.level() / 2).max(1);
+                for (die, _) in self.hit_dice() {
+                    let spent = self.spent_hit_dice.entry(die).or_default();
+                    let back = regained.min(*spent);
+                    *spent -= back;
+                    regained -= back;
+                }
+            }
+        }
+
+        self.resources
+            .iter_mut()
+            .filter(|r| r.recharge().on(kind))
+            .for_each(Resource::restore);
+
+        Ok(kind)
+    }
+
+    ///
+    /// Track a limited-use resource,
+    /// replacing any with the same id.
+    ///
+    pub fn add_resource(&mut self, resource: Resource) -> &mut Self {
+        self.resources.retain(|r| r.id() != resource.id());
+        self.resources.push(resource);
+        self
+    }
+
+    pub fn resource(&self, id: &str) -> Option<&Resource> {
+        self.resources.iter().find(|r| r.id() == id)
+    }
+
+    pub fn resource_mut(&mut self, id: &str) -> Option<&mut Resource> {
+        self.resources.iter_mut().find(|r| r.id() == id)
+    }
+
+    ///
+    /// `(left, max)` uses of a resource.
+    ///
+    pub fn uses(&self, id: &str) -> Option<(usize, usize)> {
+        self.resource(id).map(|r| (r.left(self), r.max(self)))
+    }
+
+    ///
+    /// Spend a use of a resource, returning how many are left.
+    ///
+    pub fn spend(&mut self, id: &'static str) -> Result<usize, ResourceError> {
+        let (left, _) = self.uses(id).ok_or(ResourceError::Unknown(id))?;
+        let resource = self.resource_mut(id).ok_or(ResourceError::Unknown(id))?;
+
+        match left {
+            0 => Err(ResourceError::Exhausted(resource.id())),
+            left => {
+                resource.spend();
+                Ok(left - 1)
+            }
+        }
+    }
+
+    pub fn exhaustion(&self) -> u8 {
+        self.exhaustion
+    }
+
+    ///
+    /// Gain levels of exhaustion, up to [MAX_EXHAUSTION],
+    /// which kills the creature.
+    ///
+    pub fn exhaust(&mut self, levels: u8) -> &mut Self {
+        self.exhaustion = self.exhaustion.saturating_add(levels).min(MAX_EXHAUSTION);
+        if self.exhaustion == MAX_EXHAUSTION {
+            self.death_saves.die();
+        }
+        self
+    }
+
+    pub fn recover(&mut self, levels: u8) -> &mut Self {
+        self.exhaustion = self.exhaustion.saturating_sub(levels);
+        self
+    }
+
+    pub fn proficiencies(&mut self) -> &mut Proficiencies {
+        &mut self.proficiencies
+    }
+
+    pub fn stats(&mut self) -> &mut HashMap<&'static str, usize> {
+        &mut self.stats
     }
 }
 
 #[cfg(test)]
 mod tests {
 
-    use std::collections::HashMap;
-
     use crate::{
         ability::{
-            skills::{History, Performance},
+            skills::{History, Perception, Performance, SleightOfHand},
             Charisma, Checks, Constitution, Dexterity, Intelligence, Strength, Wisdom, Saves,
         },
+        creature::{
+            context::{CheckContext, Situational},
+            effect,
+            proficiency::{Expertise, Full, IntoConditional},
+        },
+        dice::{modifiers::Arithmetic, Roll, D20, D4, D8},
         identity::Identity,
+        item::{
+            tool::{ThievesTools, Tool},
+            weapon::{Longsword, Weapon, WeaponCategory},
+            Tools, Weapons,
+        },
+        language::{Common, Languages},
+        spell::{
+            slots::{Caster, Progression},
+            Bless, Fireball, HoldPerson,
+        },
     };
 
-    use super::{proficiency::Proficiencies, Creature};
+    use super::{
+        concentration::{Event, Reason},
+        Creature,
+    };
 
     #[test]
     fn check_and_save() {
-        let mut ent = Creature(HashMap::default(), Proficiencies::default());
+        let mut ent = Creature::default();
 
         ent.stats().extend(vec![
             (Strength.id(), 2),
@@ -121,9 +1517,21 @@ mod tests {
         println!("{:?}", ent.check(History));
     }
 
+    #[test]
+    fn save_modifier() {
+        let mut ent = Creature::default();
+        ent.stats().insert(Wisdom.id(), 16);
+        ent.proficiencies().insert(Saves(Wisdom));
+
+        // WIS +3, and proficiency.
+        let save = ent.save(Wisdom);
+        let d20 = save[D20][0].value();
+        assert_eq!(save.total(), d20 + 3 + 2);
+    }
+
     #[test]
     fn proficiency() {
-        let mut ent = Creature(HashMap::default(), Proficiencies::default());
+        let mut ent = Creature::default();
 
         ent.proficiencies()
             .insert(Saves(Dexterity))
@@ -133,4 +1541,261 @@ mod tests {
 
         // println!("Proficient in DEXTERITY, {}", Dexterity::proficient(&ent));
     }
+
+    #[test]
+    fn equipment_proficiency() {
+        let mut ent = Creature::default();
+
+        assert!(!Longsword.proficient(&ent));
+
+        ent.proficiencies()
+            .insert(Weapons(WeaponCategory::Martial))
+            .insert(Languages(Common));
+
+        assert!(Longsword.proficient(&ent));
+        assert!(ent.proficient(Languages(Common)).is_some());
+        assert!(!ThievesTools.proficient(&ent));
+    }
+
+    #[test]
+    fn tool_check() {
+        let mut ent = Creature::default();
+        ent.stats().insert(Dexterity.id(), 16);
+
+        ent.proficiencies().insert(Tools(ThievesTools));
+
+        let single = ent.tool_check(ThievesTools, SleightOfHand);
+        assert_eq!(single[D20].len(), 1);
+        assert!((6..=25).contains(&single.total()));
+
+        ent.proficiencies().insert(Checks(SleightOfHand));
+
+        let both = ent.tool_check(ThievesTools, SleightOfHand);
+        assert_eq!(both[D20].len(), 2);
+        assert!((6..=25).contains(&both.total()));
+    }
+
+    #[test]
+    fn conditional_proficiency() {
+        let mut dwarf = Creature::default();
+        dwarf.stats().insert(Intelligence.id(), 10);
+
+        dwarf
+            .proficiencies()
+            .insert(Checks(History).when(|ctx| ctx.tagged("5E::TAG::STONEWORK"), Expertise));
+        dwarf.proficiencies().insert(
+            Checks(SleightOfHand)
+                .when(|ctx| ctx.uses(&ThievesTools), Expertise)
+                .otherwise(Full),
+        );
+
+        let stonework = CheckContext::default().tag("5E::TAG::STONEWORK");
+        let bonus = |ctx: &CheckContext, grants: Vec<&super::Grant>| {
+            grants[0].prof_type().bonus(&dwarf, ctx, 2)
+        };
+
+        assert_eq!(bonus(&stonework, dwarf.explain(Checks(History))), 4);
+        assert_eq!(
+            bonus(&CheckContext::default(), dwarf.explain(Checks(History))),
+            0
+        );
+
+        let picking = CheckContext::default().using(ThievesTools);
+        assert_eq!(bonus(&picking, dwarf.explain(Checks(SleightOfHand))), 4);
+        assert_eq!(
+            bonus(
+                &CheckContext::default(),
+                dwarf.explain(Checks(SleightOfHand))
+            ),
+            2
+        );
+
+        assert!((5..=24).contains(&dwarf.check_in(History, stonework).total()));
+
+        // Proficient in History anyway: expertise only helps with stonework.
+        dwarf.proficiencies().insert(Checks(History));
+
+        let plain = dwarf.check(History);
+        let d20 = plain[D20][0].value();
+        assert_eq!(plain.total(), d20 + 2);
+
+        let stonework = dwarf.check_in(History, CheckContext::default().tag("5E::TAG::STONEWORK"));
+        let d20 = stonework[D20][0].value();
+        assert_eq!(stonework.total(), d20 + 4);
+    }
+
+    #[test]
+    fn situational_modifiers() {
+        let mut ent = Creature::default();
+        ent.stats().insert(Wisdom.id(), 10);
+
+        ent.add_situational(Situational::new("HOMEBREW::KEEN_SMELL", |_, ctx| {
+            ctx.tagged("HOMEBREW::TAG::SMELL")
+                .then(|| Box::new(Arithmetic::Add(5)) as _)
+        }));
+
+        let smell = CheckContext::default().tag("HOMEBREW::TAG::SMELL");
+        assert!((6..=25).contains(&ent.check_in(Perception, smell).total()));
+        assert!((1..=20).contains(&ent.check(Perception).total()));
+    }
+
+    #[test]
+    fn spellcasting() {
+        let mut wizard = Creature::default();
+        wizard.stats().insert(Intelligence.id(), 16);
+
+        assert_eq!(wizard.spell_save_dc("5E::CLASS::WIZARD"), None);
+
+        wizard.spellcasting().add(Caster::new(
+            "5E::CLASS::WIZARD",
+            Intelligence,
+            Progression::Full,
+            1,
+        ));
+
+        assert_eq!(wizard.spell_save_dc("5E::CLASS::WIZARD"), Some(13));
+        assert_eq!(wizard.spell_attack_bonus("5E::CLASS::WIZARD"), Some(5));
+        assert!(wizard
+            .spell_attack("5E::CLASS::WIZARD")
+            .is_some_and(|r| (6..=25).contains(&r.total())));
+    }
+
+    #[test]
+    fn concentration() {
+        let mut cleric = Creature::default();
+        cleric.stats().insert(Constitution.id(), 10);
+
+        assert!(!cleric.concentrate(&Fireball));
+        assert!(cleric.concentrate(&Bless));
+        assert!(cleric.concentrate(&HoldPerson));
+        assert_eq!(
+            cleric.concentration().map(|c| c.spell()),
+            Some(HoldPerson.id())
+        );
+
+        assert_eq!(
+            cleric.events(),
+            vec![
+                Event::Started(Bless.id()),
+                Event::Ended(Bless.id(), Reason::Replaced(HoldPerson.id())),
+                Event::Started(HoldPerson.id()),
+            ]
+        );
+
+        // DC 30: a +0 CON save can't make it.
+        cleric.take_damage(60);
+        assert!(cleric.concentration().is_none());
+        assert!(matches!(
+            cleric.events()[..],
+            [Event::Ended(_, Reason::FailedSave { dc: 30, .. })]
+        ));
+
+        cleric.take_damage(5);
+        assert!(cleric.events().is_empty());
+
+        cleric.concentrate(&Bless);
+        cleric.take_damage(1);
+        match cleric.events()[..] {
+            [_, Event::Maintained { roll, dc: 10, .. }] => assert!(roll >= 10),
+            [_, Event::Ended(_, Reason::FailedSave { roll, dc: 10 })] => assert!(roll < 10),
+            ref other => panic!("unexpected events: {other:?}"),
+        }
+
+        cleric.concentrate(&Bless);
+        cleric.incapacitate();
+        assert_eq!(
+            cleric.events().last(),
+            Some(&Event::Ended(Bless.id(), Reason::Incapacitated))
+        );
+
+        // No damage, no save.
+        cleric.concentrate(&Bless);
+        cleric.take_damage(0);
+        assert_eq!(cleric.events(), vec![Event::Started(Bless.id())]);
+
+        // Dropping to 0 hit points ends it without a save.
+        cleric.set_max_hp(Some(10));
+        cleric.take_damage(10);
+        assert_eq!(
+            cleric.events(),
+            vec![Event::Ended(Bless.id(), Reason::Incapacitated)]
+        );
+    }
+
+    #[test]
+    fn effects() {
+        let mut ent = Creature::default();
+        ent.stats().insert(Wisdom.id(), 10);
+
+        ent.add_effect(effect::bless("5E::CLASS::CLERIC"))
+            .add_effect(effect::guidance("5E::CLASS::DRUID"))
+            .add_effect(effect::bardic_inspiration("5E::CLASS::BARD", || D8(1)));
+
+        // Bless and Bardic Inspiration (spent on this save).
+        let save = ent.save(Wisdom);
+        assert_eq!((save[D4].len(), save[D8].len()), (1, 1));
+
+        // Guidance, once.
+        assert_eq!(ent.check(Perception)[D4].len(), 1);
+        assert_eq!(ent.check(Perception)[D4].len(), 0);
+        assert_eq!(
+            ent.effects().map(|e| e.source()).collect::<Vec<_>>(),
+            vec!["5E::CLASS::CLERIC"]
+        );
+
+        // Recasting doesn't stack.
+        ent.add_effect(effect::bless("5E::CLASS::PALADIN"));
+        assert_eq!(ent.save(Wisdom)[D4].len(), 1);
+
+        ent.start_turn();
+        (0..10).for_each(|_| ent.end_turn());
+        assert_eq!(ent.effects().count(), 0);
+
+        ent.add_effect(effect::bless("5E::CLASS::CLERIC"));
+        ent.remove_effects("5E::CLASS::CLERIC");
+        assert_eq!(ent.save(Wisdom)[D4].len(), 0);
+    }
+
+    #[test]
+    fn roll_hooks() {
+        use std::{cell::Cell, rc::Rc};
+
+        let mut ent = Creature::default();
+        ent.stats().insert(Dexterity.id(), 10);
+
+        let failures = Rc::new(Cell::new(0));
+        let counter = failures.clone();
+
+        ent.hooks()
+            .before_roll("HOMEBREW::DANGER_SENSE", |_, kind, ctx| match kind {
+                super::RollKind::Save => ctx.advantage(),
+                _ => ctx,
+            })
+            .after_roll("5E::FEATURE::PORTENT", |_, _, _, rolls| {
+                rolls.replace(&D20, [Roll::from(4)])
+            })
+            .after_result("HOMEBREW::GRUDGE", move |_, _, _, outcome| {
+                if !outcome.success() {
+                    counter.set(counter.get() + 1);
+                }
+                outcome
+            });
+
+        // Portent replaces the d20s, advantage included.
+        assert_eq!(ent.save(Dexterity)[D20].len(), 1);
+        assert_eq!(ent.check(Dexterity).total(), 4);
+
+        assert!(!ent
+            .save_against(Dexterity, CheckContext::default(), 5)
+            .success());
+        assert!(ent.attack_against(3, CheckContext::default(), 7).success());
+        assert_eq!(failures.get(), 1);
+
+        ent.hooks().remove("5E::FEATURE::PORTENT");
+        assert_eq!(
+            ent.hooks().ids(),
+            vec!["HOMEBREW::DANGER_SENSE", "HOMEBREW::GRUDGE"]
+        );
+        assert_eq!(ent.save(Dexterity)[D20].len(), 2);
+    }
 }
@@ -1,11 +1,11 @@
 use core::hash::Hash;
 use std::{collections::HashMap, marker::PhantomData};
 
-use xander_macros::ProficiencyType;
+use xander_macros::{identify, ProficiencyType};
 
-use crate::identity::Identity;
+use crate::{ability::Ability, identity::Identity};
 
-use super::Creature;
+use super::{context::CheckContext, Creature};
 
 ///
 /// ## Proficiency
@@ -14,18 +14,18 @@ use super::Creature;
 /// ***
 ///
 /// Use this trait, in co-ordination with the
-/// `proficiency!` macro to make a new proficiency:
+/// `#[Proficiency]` macro to make a new proficiency:
 ///
 /// ```
-/// use xander_macros::proficiency;
+/// use xander_macros::Proficiency;
 /// use xander::{
 ///     creature::proficiency::Proficiency,
 ///     identity::Identity,
 ///     ability::Ability
 /// };
 ///
-///
-/// proficiency!(SavingThrows(Ability));
+/// #[Proficiency("HOMEBREW::PROFICIENCY::SAVING_THROWS")]
+/// pub struct SavingThrows(Ability);
 /// ```
 ///
 pub trait Proficiency<P>: Identity + Hash + Eq
@@ -51,6 +51,7 @@ where
 /// ```
 /// use xander::{
 ///     creature::{
+///         context::CheckContext,
 ///         proficiency::{
 ///             Proficiency, ProficiencyType,
 ///             ProficiencyTyped,
@@ -71,9 +72,13 @@ where
 /// }
 ///
 /// impl ProficiencyType for Triple {
-///     fn bonus(_ : &Creature, bonus : i32) -> i32 {
+///     fn bonus(&self, _ : &Creature, _ : &CheckContext, bonus : i32) -> i32 {
 ///         bonus * 3
 ///     }
+///
+///     fn rank(&self) -> u8 {
+///         4
+///     }
 /// }
 /// ```
 ///
@@ -82,7 +87,21 @@ pub trait ProficiencyType: Identity {
     /// Modify the original proficiency bonus,
     /// in some manner.
     ///
-    fn bonus(&self, ent: &Creature, prof_bonus: i32) -> i32;
+    /// `ctx` describes the roll being made
+    /// (see [Conditional] for a type that depends on it).
+    ///
+    fn bonus(&self, ent: &Creature, ctx: &CheckContext, prof_bonus: i32) -> i32;
+
+    ///
+    /// Precedence of this type, when a creature
+    /// is granted the same proficiency more than once.
+    ///
+    /// The highest rank wins:
+    /// [Expertise] > [Full] > [HalfRoundedUp] > [Half].
+    ///
+    fn rank(&self) -> u8 {
+        2
+    }
 
     fn boxed(self) -> Box<dyn ProficiencyType>
     where
@@ -133,42 +152,399 @@ where
     }
 }
 
+///
+/// Where a proficiency came from.
+///
+/// Each variant holds the id of whatever granted it.
+///
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
+pub enum Source {
+    Class(&'static str),
+    Background(&'static str),
+    Race(&'static str),
+    Feat(&'static str),
+    ///
+    /// A class feature (e.g. Jack of All Trades).
+    ///
+    Feature(&'static str),
+    ///
+    /// One of several picks of the same feature, by feature
+    /// id and what was picked (e.g. each Expertise, by skill).
+    ///
+    Pick(&'static str, &'static str),
+    ///
+    /// Inserted directly, with no particular origin.
+    ///
+    Manual,
+}
+
+///
+/// A single grant of a proficiency.
+///
 #[derive(Debug)]
-pub struct Proficiencies(HashMap<&'static str, HashMap<&'static str, Box<dyn ProficiencyType>>>);
+pub struct Grant {
+    source: Source,
+    prof_type: Box<dyn ProficiencyType>,
+}
+
+impl Grant {
+    pub fn source(&self) -> Source {
+        self.source
+    }
+
+    pub fn prof_type(&self) -> &dyn ProficiencyType {
+        self.prof_type.as_ref()
+    }
+}
+
+///
+/// Which base abilities a [Fallback] covers.
+///
+pub type Scope = Box<dyn Fn(&dyn Ability) -> bool>;
+
+///
+/// A proficiency type that applies to everything in a
+/// category the creature isn't otherwise proficient in.
+///
+/// ***
+/// **Example**: Jack of All Trades, a [Half] fallback
+/// for [crate::ability::CHECKS].
+///
+pub struct Fallback {
+    grant: Grant,
+    scope: Option<Scope>,
+}
+
+impl Fallback {
+    pub fn new(source: Source, prof_type: impl ProficiencyType + 'static) -> Self {
+        Self {
+            grant: Grant {
+                source,
+                prof_type: prof_type.boxed(),
+            },
+            scope: None,
+        }
+    }
+
+    ///
+    /// Only apply when the base ability
+    /// passes `scope`.
+    ///
+    /// ***
+    /// **Example**: Remarkable Athlete only covers
+    /// STR, DEX and CON checks.
+    ///
+    pub fn only(mut self, scope: impl Fn(&dyn Ability) -> bool + 'static) -> Self {
+        self.scope = Some(Box::new(scope));
+        self
+    }
 
-impl Default for Proficiencies {
-    fn default() -> Self {
-        Self(Default::default())
+    pub fn grant(&self) -> &Grant {
+        &self.grant
+    }
+
+    ///
+    /// Does this fallback cover something based on `ability`?
+    ///
+    /// Scoped fallbacks never cover categories
+    /// without a base ability.
+    ///
+    pub fn covers(&self, ability: Option<&dyn Ability>) -> bool {
+        match (&self.scope, ability) {
+            (None, _) => true,
+            (Some(scope), Some(ability)) => scope(ability),
+            (Some(_), None) => false,
+        }
+    }
+}
+
+impl std::fmt::Debug for Fallback {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Fallback")
+            .field("grant", &self.grant)
+            .field("scoped", &self.scope.is_some())
+            .finish()
     }
 }
 
+type OfferFn = dyn Fn(&mut Proficiencies, Source);
+
+///
+/// A proficiency on offer, to be granted later:
+/// e.g. one of a class's skill choices.
+///
+/// ***
+/// ```
+/// use xander::{
+///     ability::{skills::Stealth, Checks},
+///     creature::proficiency::{Offer, Proficiencies, Source},
+/// };
+///
+/// let stealth = Offer::new(Checks(Stealth));
+/// assert_eq!(stealth.id(), "5E::SKILL::STEALTH");
+///
+/// let mut profs = Proficiencies::default();
+/// stealth.grant(&mut profs, Source::Class("5E::CLASS::ROGUE"));
+/// assert!(profs.has(Checks(Stealth)).is_some());
+/// ```
+///
+pub struct Offer {
+    category: &'static str,
+    id: &'static str,
+    grant: Box<OfferFn>,
+}
+
+impl Offer {
+    pub fn new<I, P>(prof: P) -> Self
+    where
+        I: Identity + Hash + Eq + 'static,
+        P: Proficiency<I> + Clone + 'static,
+    {
+        Self {
+            category: P::__id(),
+            id: prof.value().id(),
+            grant: Box::new(move |profs, source| {
+                profs.grant(source, prof.clone());
+            }),
+        }
+    }
+
+    ///
+    /// Category id, e.g. [crate::ability::CHECKS].
+    ///
+    pub fn category(&self) -> &'static str {
+        self.category
+    }
+
+    ///
+    /// Id of the thing offered, e.g. a skill.
+    ///
+    pub fn id(&self) -> &'static str {
+        self.id
+    }
+
+    pub fn grant(&self, profs: &mut Proficiencies, source: Source) {
+        (self.grant)(profs, source)
+    }
+}
+
+impl std::fmt::Debug for Offer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Offer({}, {})", self.category, self.id)
+    }
+}
+
+///
+/// All the proficiencies of a creature,
+/// by category.
+///
+/// The same proficiency can be granted by several
+/// sources; the highest [ProficiencyType::rank] is
+/// the one that applies, or the highest bonus when
+/// rolling (see [Proficiencies::resolve_by]).
+///
+#[derive(Debug, Default)]
+pub struct Proficiencies {
+    grants: HashMap<&'static str, HashMap<&'static str, Vec<Grant>>>,
+    fallbacks: HashMap<&'static str, Vec<Fallback>>,
+}
+
 impl Proficiencies {
+    ///
+    /// Insert a proficiency with no particular source.
+    ///
     pub fn insert<T, I, P>(&mut self, prof: impl IntoProficiencyTyped<T, I, P>) -> &mut Self
+    where
+        T: ProficiencyType + 'static,
+        I: Identity + Hash + Eq,
+        P: Proficiency<I>,
+    {
+        self.grant(Source::Manual, prof)
+    }
+
+    ///
+    /// Grant a proficiency from `source`.
+    ///
+    /// Grants never overwrite each other: granting the
+    /// exact same thing twice from one source is a no-op,
+    /// anything else is kept alongside for [Self::explain].
+    ///
+    pub fn grant<T, I, P>(
+        &mut self,
+        source: Source,
+        prof: impl IntoProficiencyTyped<T, I, P>,
+    ) -> &mut Self
     where
         T: ProficiencyType + 'static,
         I: Identity + Hash + Eq,
         P: Proficiency<I>,
     {
         let ProficiencyTyped(prof_type, prof, _) = prof.into_proficiency_typed();
-        let cat = self.0.entry(P::__id()).or_insert(HashMap::new());
+        let grants = self
+            .grants
+            .entry(P::__id())
+            .or_default()
+            .entry(prof.value().id())
+            .or_default();
+
+        if !grants
+            .iter()
+            .any(|g| g.source == source && g.prof_type.id() == prof_type.id())
+        {
+            grants.push(Grant {
+                source,
+                prof_type: prof_type.boxed(),
+            });
+        }
 
-        cat.insert(prof.value().id(), prof_type.boxed());
         self
     }
 
+    ///
+    /// Add a fallback for a whole category,
+    /// given by its id (e.g. [crate::ability::CHECKS]).
+    ///
+    pub fn fallback(&mut self, category: &'static str, fallback: Fallback) -> &mut Self {
+        self.fallbacks.entry(category).or_default().push(fallback);
+        self
+    }
+
+    ///
+    /// Remove everything granted by `source`,
+    /// fallbacks included.
+    ///
+    pub fn remove(&mut self, source: Source) -> &mut Self {
+        self.grants.values_mut().for_each(|cat| {
+            cat.values_mut()
+                .for_each(|grants| grants.retain(|g| g.source != source));
+            cat.retain(|_, grants| !grants.is_empty());
+        });
+        self.grants.retain(|_, cat| !cat.is_empty());
+
+        self.fallbacks
+            .values_mut()
+            .for_each(|fallbacks| fallbacks.retain(|f| f.grant.source != source));
+        self.fallbacks.retain(|_, fallbacks| !fallbacks.is_empty());
+
+        self
+    }
+
+    ///
+    /// The type of proficiency that applies, if any.
+    ///
+    /// Only considers explicit grants, see [Self::resolve]
+    /// to take fallbacks into account.
+    ///
     pub fn has<I, P>(&self, prof: P) -> Option<&dyn ProficiencyType>
     where
         I: Identity + Hash + Eq,
         P: Proficiency<I>,
     {
-        self.0
-            .get(P::__id())
-            .and_then(|cat| cat.get(prof.value().id()).map(Box::as_ref))
+        self.explain(prof).first().map(|g| g.prof_type())
+    }
+
+    ///
+    /// The grant that applies to `prof`: the highest ranked of
+    /// its explicit grants and the fallbacks covering `ability`.
+    ///
+    /// Ties go to explicit grants, then to whichever came first.
+    ///
+    pub fn resolve<I, P>(&self, prof: P, ability: Option<&dyn Ability>) -> Option<&Grant>
+    where
+        I: Identity + Hash + Eq,
+        P: Proficiency<I>,
+    {
+        self.resolve_id(P::__id(), prof.value().id(), ability)
+    }
+
+    ///
+    /// Same as [Self::resolve], by category and id
+    /// (e.g. from a [CheckContext]).
+    ///
+    pub fn resolve_id(
+        &self,
+        category: &str,
+        id: &str,
+        ability: Option<&dyn Ability>,
+    ) -> Option<&Grant> {
+        self.resolve_id_by(category, id, ability, |g| g.prof_type.rank() as i32)
+    }
+
+    ///
+    /// Same as [Self::resolve], picking whichever grant `score`
+    /// rates highest (e.g. the bonus it gives to a roll).
+    ///
+    pub fn resolve_by<I, P>(
+        &self,
+        prof: P,
+        ability: Option<&dyn Ability>,
+        score: impl Fn(&Grant) -> i32,
+    ) -> Option<&Grant>
+    where
+        I: Identity + Hash + Eq,
+        P: Proficiency<I>,
+    {
+        self.resolve_id_by(P::__id(), prof.value().id(), ability, score)
+    }
+
+    ///
+    /// Same as [Self::resolve_by], by category and id.
+    ///
+    pub fn resolve_id_by(
+        &self,
+        category: &str,
+        id: &str,
+        ability: Option<&dyn Ability>,
+        score: impl Fn(&Grant) -> i32,
+    ) -> Option<&Grant> {
+        let fallbacks = self
+            .fallbacks
+            .get(category)
+            .into_iter()
+            .flatten()
+            .filter(|f| f.covers(ability))
+            .map(Fallback::grant);
+
+        // `max_by_key` keeps the last of equals.
+        self.explain_id(category, id)
+            .into_iter()
+            .chain(fallbacks)
+            .rev()
+            .max_by_key(|g| score(g))
+    }
+
+    ///
+    /// Every grant of this proficiency, highest precedence first.
+    ///
+    /// The first grant is the one that applies regardless of
+    /// circumstances; ties go to whichever was granted first.
+    ///
+    pub fn explain<I, P>(&self, prof: P) -> Vec<&Grant>
+    where
+        I: Identity + Hash + Eq,
+        P: Proficiency<I>,
+    {
+        self.explain_id(P::__id(), prof.value().id())
+    }
+
+    ///
+    /// Same as [Self::explain], by category and id.
+    ///
+    pub fn explain_id(&self, category: &str, id: &str) -> Vec<&Grant> {
+        let mut grants = self
+            .grants
+            .get(category)
+            .and_then(|cat| cat.get(id))
+            .map(|grants| grants.iter().collect::<Vec<_>>())
+            .unwrap_or_default();
+
+        grants.sort_by_key(|g| std::cmp::Reverse(g.prof_type.rank()));
+        grants
     }
 }
 
 #[ProficiencyType("5E::PROFICIENCY_TYPE::FULL")]
-pub fn Full(&self, _: &Creature, bonus: i32) -> i32 {
+pub fn Full(&self, _: &Creature, _: &CheckContext, bonus: i32) -> i32 {
     bonus
 }
 
@@ -187,30 +563,161 @@ where
 ///
 /// Half-proficiency: from the "Jack of All Trades" feat.
 ///
-#[ProficiencyType("5E::PROFICIENCY_TYPE::Half")]
-pub fn Half(&self, _: &Creature, bonus: i32) -> i32 {
+#[ProficiencyType("5E::PROFICIENCY_TYPE::Half", rank = 0)]
+pub fn Half(&self, _: &Creature, _: &CheckContext, bonus: i32) -> i32 {
     bonus.div_floor(2)
 }
 
+///
+/// Half-proficiency, rounded up: from the
+/// "Remarkable Athlete" feature.
+///
+#[ProficiencyType("5E::PROFICIENCY_TYPE::HALF_ROUNDED_UP", rank = 1)]
+pub fn HalfRoundedUp(&self, _: &Creature, _: &CheckContext, bonus: i32) -> i32 {
+    bonus.div_ceil(2)
+}
+
 ///
 /// Expertise from the Rogue features.
 ///
-#[ProficiencyType("5E::PROFICIENCY_TYPE::EXPERTISE")]
-pub fn Expertise(&self, _: &Creature, bonus: i32) -> i32 {
+#[ProficiencyType("5E::PROFICIENCY_TYPE::EXPERTISE", rank = 3)]
+pub fn Expertise(&self, _: &Creature, _: &CheckContext, bonus: i32) -> i32 {
     bonus * 2
 }
 
+///
+/// A proficiency type that depends on the circumstances of the roll,
+/// contributing nothing (or `otherwise`) when they aren't met.
+///
+/// ***
+/// **Example**: Dwarven Stonecunning, double proficiency
+/// on History checks about the origin of stonework:
+/// ```
+/// use xander::{
+///     ability::{skills::History, Checks},
+///     creature::proficiency::{Expertise, IntoConditional, Proficiencies},
+/// };
+///
+/// let mut profs = Proficiencies::default();
+/// profs.insert(Checks(History).when(|ctx| ctx.tagged("5E::TAG::STONEWORK"), Expertise));
+/// ```
+///
+pub struct Conditional {
+    when: Box<dyn Fn(&CheckContext) -> bool>,
+    then: Box<dyn ProficiencyType>,
+    otherwise: Option<Box<dyn ProficiencyType>>,
+}
+
+impl Conditional {
+    pub fn new(
+        when: impl Fn(&CheckContext) -> bool + 'static,
+        then: impl ProficiencyType + 'static,
+    ) -> Self {
+        Self {
+            when: Box::new(when),
+            then: then.boxed(),
+            otherwise: None,
+        }
+    }
+
+    ///
+    /// Type to use when the condition isn't met.
+    ///
+    pub fn otherwise(mut self, otherwise: impl ProficiencyType + 'static) -> Self {
+        self.otherwise = Some(otherwise.boxed());
+        self
+    }
+}
+
+impl std::fmt::Debug for Conditional {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Conditional")
+            .field("then", &self.then)
+            .field("otherwise", &self.otherwise)
+            .finish()
+    }
+}
+
+identify!(Conditional, "5E::PROFICIENCY_TYPE::CONDITIONAL");
+
+impl ProficiencyType for Conditional {
+    fn bonus(&self, ent: &Creature, ctx: &CheckContext, prof_bonus: i32) -> i32 {
+        match (self.when)(ctx) {
+            true => self.then.bonus(ent, ctx, prof_bonus),
+            false => self
+                .otherwise
+                .as_ref()
+                .map(|t| t.bonus(ent, ctx, prof_bonus))
+                .unwrap_or(0),
+        }
+    }
+
+    ///
+    /// Ranked by what it gives regardless of
+    /// the circumstances: `otherwise`, or nothing.
+    ///
+    fn rank(&self) -> u8 {
+        self.otherwise.as_ref().map(|t| t.rank()).unwrap_or(0)
+    }
+}
+
+pub trait IntoConditional<I, P>
+where
+    I: Identity + Hash + Eq,
+    P: Proficiency<I>,
+{
+    ///
+    /// Only apply `then` when `when` holds for the roll.
+    ///
+    fn when(
+        self,
+        when: impl Fn(&CheckContext) -> bool + 'static,
+        then: impl ProficiencyType + 'static,
+    ) -> ProficiencyTyped<Conditional, I, P>;
+}
+
+impl<I, P> IntoConditional<I, P> for P
+where
+    I: Identity + Hash + Eq,
+    P: Proficiency<I>,
+{
+    fn when(
+        self,
+        when: impl Fn(&CheckContext) -> bool + 'static,
+        then: impl ProficiencyType + 'static,
+    ) -> ProficiencyTyped<Conditional, I, P> {
+        ProficiencyTyped(Conditional::new(when, then), self, PhantomData::<I>)
+    }
+}
+
+impl<I, P> ProficiencyTyped<Conditional, I, P>
+where
+    I: Identity + Hash + Eq,
+    P: Proficiency<I>,
+{
+    ///
+    /// Type to use when the condition isn't met.
+    ///
+    pub fn otherwise(self, otherwise: impl ProficiencyType + 'static) -> Self {
+        let ProficiencyTyped(conditional, prof, marker) = self;
+        ProficiencyTyped(conditional.otherwise(otherwise), prof, marker)
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use std::collections::HashMap;
+    use core::hash::Hash;
 
     use crate::{
         ability::{
-            skills::{History, Persuasion, Stealth},
-            Charisma, Checks, Constitution, Dexterity, Intelligence, Strength, Wisdom,
+            skills::{Athletics, History, Persuasion, Stealth},
+            Ability, Charisma, Check, Checks, Constitution, Dexterity, Intelligence, Strength,
+            Wisdom, CHECKS,
         },
         creature::{
-            proficiency::{IntoExpertise, IntoHalf},
+            proficiency::{
+                Expertise, Fallback, Full, Half, HalfRoundedUp, IntoExpertise, IntoHalf, Source,
+            },
             Creature,
         },
         dice::{modifiers::Advantage, D20},
@@ -221,7 +728,7 @@ mod tests {
 
     #[test]
     fn skill_proficiencies() {
-        let mut rogue = Creature(HashMap::new(), Proficiencies::default());
+        let mut rogue = Creature::default();
 
         rogue.stats().extend(vec![
             (Strength.id(), 2),
@@ -246,4 +753,100 @@ mod tests {
 
         // println!("{rogue:?}")
     }
+
+    #[test]
+    fn precedence() {
+        let mut profs = Proficiencies::default();
+
+        profs.insert(Checks(History)).insert(Checks(History).half());
+
+        assert_eq!(profs.has(Checks(History)).map(|t| t.id()), Some(Full.id()));
+
+        profs.grant(
+            Source::Class("5E::CLASS::ROGUE"),
+            Checks(History).expertise(),
+        );
+
+        assert_eq!(
+            profs.has(Checks(History)).map(|t| t.id()),
+            Some(Expertise.id())
+        );
+    }
+
+    #[test]
+    fn sources() {
+        let mut profs = Proficiencies::default();
+
+        profs
+            .grant(Source::Background("5E::BACKGROUND::SAGE"), Checks(History))
+            .grant(Source::Feat("5E::FEAT::SKILLED"), Checks(History))
+            .grant(Source::Feat("5E::FEAT::SKILLED"), Checks(Persuasion));
+
+        let why = profs
+            .explain(Checks(History))
+            .iter()
+            .map(|g| g.source())
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            why,
+            vec![
+                Source::Background("5E::BACKGROUND::SAGE"),
+                Source::Feat("5E::FEAT::SKILLED")
+            ]
+        );
+
+        profs.remove(Source::Feat("5E::FEAT::SKILLED"));
+
+        assert!(profs.has(Checks(History)).is_some());
+        assert!(profs.has(Checks(Persuasion)).is_none());
+    }
+
+    #[test]
+    fn fallbacks() {
+        let mut profs = Proficiencies::default();
+
+        profs
+            .insert(Checks(History))
+            .fallback(
+                CHECKS,
+                Fallback::new(Source::Class("5E::CLASS::BARD"), Half),
+            )
+            .fallback(
+                CHECKS,
+                Fallback::new(Source::Class("5E::CLASS::FIGHTER"), HalfRoundedUp).only(
+                    |ability: &dyn Ability| {
+                        [Strength.id(), Dexterity.id(), Constitution.id()].contains(&ability.id())
+                    },
+                ),
+            );
+
+        fn applied<C: Check + Hash + Eq>(
+            profs: &Proficiencies,
+            check: C,
+            ability: &dyn Ability,
+        ) -> Option<&'static str> {
+            profs
+                .resolve(Checks(check), Some(ability))
+                .map(|g| g.prof_type().id())
+        }
+
+        // Explicit proficiency always wins.
+        assert_eq!(applied(&profs, History, &Intelligence), Some(Full.id()));
+        // Rounding up outranks rounding down.
+        assert_eq!(
+            applied(&profs, Athletics, &Strength),
+            Some(HalfRoundedUp.id())
+        );
+        assert_eq!(applied(&profs, Persuasion, &Charisma), Some(Half.id()));
+
+        profs.remove(Source::Class("5E::CLASS::BARD"));
+
+        assert_eq!(
+            applied(&profs, Athletics, &Strength),
+            Some(HalfRoundedUp.id())
+        );
+        assert_eq!(applied(&profs, Persuasion, &Charisma), None);
+        assert!(profs.has(Checks(Athletics)).is_none());
+    }
 }
@@ -0,0 +1,199 @@
+use std::cell::Cell;
+
+use crate::dice::D6;
+
+use super::{rest::Recharge, Creature};
+
+///
+/// Works out how many uses a resource has,
+/// e.g. from class level or an ability modifier.
+///
+pub type UsesFn = dyn Fn(&Creature) -> usize;
+
+///
+/// Something with a limited number of uses: Ki points,
+/// Rage, Channel Divinity, a dragon's breath weapon...
+///
+/// ***
+/// ```
+/// use xander::creature::{resource::Resource, rest::Recharge, Creature};
+///
+/// let mut dragon = Creature::default();
+/// dragon.add_resource(Resource::fixed("HOMEBREW::FIRE_BREATH", 1, Recharge::Roll(5)));
+///
+/// assert_eq!(dragon.spend("HOMEBREW::FIRE_BREATH"), Ok(0));
+/// assert!(dragon.spend("HOMEBREW::FIRE_BREATH").is_err());
+///
+/// // 5 or 6 on a d6, at the start of each turn.
+/// dragon.start_turn();
+/// ```
+///
+pub struct Resource {
+    id: &'static str,
+    uses: Box<UsesFn>,
+    recharge: Recharge,
+    used: Cell<usize>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ResourceError {
+    ///
+    /// No resource with this id.
+    ///
+    Unknown(&'static str),
+    ///
+    /// Every use is spent.
+    ///
+    Exhausted(&'static str),
+}
+
+impl Resource {
+    pub fn new(
+        id: &'static str,
+        uses: impl Fn(&Creature) -> usize + 'static,
+        recharge: Recharge,
+    ) -> Self {
+        Self {
+            id,
+            uses: Box::new(uses),
+            recharge,
+            used: Cell::new(0),
+        }
+    }
+
+    ///
+    /// A set number of uses.
+    ///
+    pub fn fixed(id: &'static str, uses: usize, recharge: Recharge) -> Self {
+        Self::new(id, move |_| uses, recharge)
+    }
+
+    pub fn id(&self) -> &'static str {
+        self.id
+    }
+
+    pub fn recharge(&self) -> Recharge {
+        self.recharge
+    }
+
+    pub fn used(&self) -> usize {
+        self.used.get()
+    }
+
+    ///
+    /// Most uses `ent` can have.
+    ///
+    pub fn max(&self, ent: &Creature) -> usize {
+        (self.uses)(ent)
+    }
+
+    ///
+    /// Uses `ent` has left.
+    ///
+    pub fn left(&self, ent: &Creature) -> usize {
+        self.max(ent).saturating_sub(self.used())
+    }
+
+    ///
+    /// Takes `&self`, so hooks holding
+    /// a `&Creature` can spend uses too.
+    ///
+    pub(crate) fn spend(&self) {
+        self.used.set(self.used() + 1);
+    }
+
+    ///
+    /// Regain every use.
+    ///
+    pub fn restore(&mut self) {
+        self.used.set(0);
+    }
+
+    ///
+    /// Roll to regain every use, for [Recharge::Roll]
+    /// resources that have been used.
+    ///
+    /// Returns whether it recharged.
+    ///
+    pub fn roll_recharge(&mut self) -> bool {
+        match self.recharge {
+            Recharge::Roll(min) if self.used() > 0 && D6(1).total() >= min => {
+                self.restore();
+                true
+            }
+            _ => false,
+        }
+    }
+}
+
+impl std::fmt::Debug for Resource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Resource")
+            .field("id", &self.id)
+            .field("recharge", &self.recharge)
+            .field("used", &self.used())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        ability::{Charisma, Dexterity},
+        creature::{
+            rest::{Recharge, RestKind},
+            Creature,
+        },
+        identity::Identity,
+    };
+
+    use super::{Resource, ResourceError};
+
+    const FLURRY: &str = "HOMEBREW::FLURRY";
+
+    #[test]
+    fn spending() {
+        let mut ent = Creature::default();
+        ent.stats().insert(Charisma.id(), 16);
+        ent.add_resource(Resource::new(
+            FLURRY,
+            |ent| ent.modifier(Charisma).unwrap_or(0).max(1) as usize,
+            Recharge::LongRest,
+        ));
+
+        assert_eq!(ent.uses(FLURRY), Some((3, 3)));
+        assert_eq!(ent.spend(FLURRY), Ok(2));
+
+        // Formulas follow the creature.
+        ent.stats().insert(Charisma.id(), 8);
+        assert_eq!(ent.uses(FLURRY), Some((0, 1)));
+        assert_eq!(ent.spend(FLURRY), Err(ResourceError::Exhausted(FLURRY)));
+        assert_eq!(
+            ent.spend(Dexterity.id()),
+            Err(ResourceError::Unknown(Dexterity.id()))
+        );
+
+        ent.start_rest(RestKind::Short).unwrap();
+        ent.finish_rest().unwrap();
+        assert_eq!(ent.uses(FLURRY), Some((0, 1)));
+
+        ent.start_rest(RestKind::Long).unwrap();
+        ent.finish_rest().unwrap();
+        assert_eq!(ent.uses(FLURRY), Some((1, 1)));
+    }
+
+    #[test]
+    fn recharge_roll() {
+        let mut breath = Resource::fixed(FLURRY, 1, Recharge::Roll(1));
+        assert!(!breath.roll_recharge());
+
+        breath.spend();
+        assert!(breath.roll_recharge());
+        assert_eq!(breath.used(), 0);
+
+        let mut never = Resource::fixed(FLURRY, 1, Recharge::Roll(7));
+        never.spend();
+        assert!(!(0..20).any(|_| never.roll_recharge()));
+        assert!(!Recharge::Roll(5).on(RestKind::Long));
+    }
+}
@@ -0,0 +1,251 @@
+///
+/// Most levels of exhaustion: the sixth is death.
+///
+pub const MAX_EXHAUSTION: u8 = 6;
+
+///
+/// Strenuous activity (walking, fighting, casting) a long
+/// rest can take before it's interrupted, in minutes.
+///
+pub const LONG_REST_INTERRUPT: u32 = 60;
+
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
+pub enum RestKind {
+    ///
+    /// At least an hour of light activity.
+    ///
+    Short,
+    ///
+    /// At least eight hours, mostly sleeping.
+    ///
+    Long,
+}
+
+///
+/// When the uses of a feature or resource come back.
+///
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
+pub enum Recharge {
+    ///
+    /// On a short or long rest.
+    ///
+    ShortRest,
+    ///
+    /// On a long rest only.
+    ///
+    LongRest,
+    ///
+    /// At the start of each turn, on a d6 roll of at
+    /// least this (e.g. 5 for "Recharge 5–6").
+    ///
+    Roll(i32),
+    ///
+    /// At the start of each turn (e.g. legendary actions).
+    ///
+    Turn,
+}
+
+impl Recharge {
+    ///
+    /// Does this come back on a `kind` rest?
+    ///
+    pub fn on(&self, kind: RestKind) -> bool {
+        matches!(
+            (self, kind),
+            (Self::ShortRest, _) | (Self::LongRest, RestKind::Long)
+        )
+    }
+}
+
+///
+/// A rest in progress.
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Rest {
+    kind: RestKind,
+    strenuous: u32,
+}
+
+impl Rest {
+    pub fn new(kind: RestKind) -> Self {
+        Self { kind, strenuous: 0 }
+    }
+
+    pub fn kind(&self) -> RestKind {
+        self.kind
+    }
+
+    ///
+    /// Minutes of strenuous activity so far.
+    ///
+    pub fn strenuous(&self) -> u32 {
+        self.strenuous
+    }
+
+    pub(crate) fn exert(&mut self, minutes: u32) {
+        self.strenuous = self.strenuous.saturating_add(minutes);
+    }
+
+    ///
+    /// Any strenuous activity interrupts a short rest,
+    /// but a long rest only after [LONG_REST_INTERRUPT] minutes.
+    ///
+    pub fn interrupted(&self) -> bool {
+        match self.kind {
+            RestKind::Short => self.strenuous > 0,
+            RestKind::Long => self.strenuous >= LONG_REST_INTERRUPT,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RestError {
+    ///
+    /// Already in the middle of a rest.
+    ///
+    Resting(RestKind),
+    ///
+    /// Not resting at all.
+    ///
+    NotResting,
+    ///
+    /// Hit dice are spent during a short rest only.
+    ///
+    NotShortRest,
+    ///
+    /// Too much activity: the rest gave no benefit.
+    ///
+    Interrupted(RestKind),
+    ///
+    /// A long rest needs at least 1 hit point to begin.
+    ///
+    Unconscious,
+    ///
+    /// No hit dice of this size left.
+    ///
+    NoHitDice(usize),
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        ability::{
+            skills::{Athletics, Perception},
+            Constitution, Intelligence, Strength,
+        },
+        class::{Fighter, Wizard},
+        creature::Creature,
+        dice::D20,
+        feature::Lucky,
+        identity::Identity,
+    };
+
+    use super::{Recharge, RestError, RestKind, MAX_EXHAUSTION};
+
+    #[test]
+    fn cadence_and_exhaustion() {
+        assert!(Recharge::ShortRest.on(RestKind::Long));
+        assert!(!Recharge::LongRest.on(RestKind::Short));
+
+        let mut ent = Creature::default();
+        ent.stats().insert(Constitution.id(), 14);
+        ent.add_level(&Fighter, &[Athletics.id(), Perception.id()])
+            .unwrap();
+        let max = ent.max_hp();
+        ent.set_speed(30).exhaust(4);
+
+        assert_eq!(ent.speed(), 15);
+        assert_eq!(ent.max_hp(), max / 2);
+        assert_eq!(ent.save(Strength)[D20].len(), 2);
+
+        ent.exhaust(1);
+        assert!(!ent.dead());
+        ent.exhaust(10);
+        assert_eq!((ent.exhaustion(), ent.speed()), (MAX_EXHAUSTION, 0));
+        assert!(ent.dead());
+    }
+
+    #[test]
+    fn short_rest() {
+        let mut ent = Creature::default();
+        ent.stats().extend([
+            (Strength.id(), 15),
+            (Constitution.id(), 14),
+            (Intelligence.id(), 13),
+        ]);
+        ent.add_level(&Fighter, &[Athletics.id(), Perception.id()])
+            .unwrap();
+        ent.add_level(&Fighter, &[]).unwrap();
+        ent.add_level(&Wizard, &[]).unwrap();
+        let max = ent.max_hp();
+        ent.take_damage(20);
+
+        assert_eq!(ent.spend_hit_die(10), Err(RestError::NotShortRest));
+        ent.start_rest(RestKind::Short).unwrap();
+        assert_eq!(
+            ent.start_rest(RestKind::Long),
+            Err(RestError::Resting(RestKind::Short))
+        );
+
+        // d10 + 2, twice.
+        let healed = ent.spend_hit_die(10).unwrap() + ent.spend_hit_die(10).unwrap();
+        assert!((6..=24).contains(&healed));
+        assert_eq!(ent.spend_hit_die(10), Err(RestError::NoHitDice(10)));
+        assert_eq!(ent.hit_dice(), [(10, 0), (6, 1)]);
+        assert_eq!(ent.finish_rest(), Ok(RestKind::Short));
+        assert_eq!(ent.hp(), (max - 20 + healed).min(max));
+
+        // Interrupted: no benefit.
+        ent.spellcasting().expend(1).unwrap();
+        ent.start_rest(RestKind::Long).unwrap();
+        ent.exert(60);
+        assert_eq!(
+            ent.finish_rest(),
+            Err(RestError::Interrupted(RestKind::Long))
+        );
+        assert_eq!(ent.spellcasting().available(1), 1);
+        assert_eq!(ent.finish_rest(), Err(RestError::NotResting));
+    }
+
+    #[test]
+    fn long_rest() {
+        let mut ent = Creature::default();
+        ent.stats().extend([
+            (Strength.id(), 15),
+            (Constitution.id(), 14),
+            (Intelligence.id(), 13),
+        ]);
+        ent.add_level(&Fighter, &[Athletics.id(), Perception.id()])
+            .unwrap();
+        ent.add_level(&Fighter, &[]).unwrap();
+        ent.add_level(&Wizard, &[]).unwrap();
+        let lucky = Lucky::new(21);
+        ent.add_feature(lucky);
+        ent.check(Strength);
+        ent.exhaust(2);
+
+        ent.start_rest(RestKind::Short).unwrap();
+        ent.spend_hit_die(10).unwrap();
+        ent.spend_hit_die(10).unwrap();
+        ent.spend_hit_die(6).unwrap();
+        ent.finish_rest().unwrap();
+
+        // Lucky only comes back on a long rest.
+        assert_eq!(ent.uses(lucky.id()), Some((2, Lucky::POINTS)));
+
+        ent.take_damage(ent.hp());
+        assert_eq!(ent.start_rest(RestKind::Long), Err(RestError::Unconscious));
+        ent.heal(1);
+
+        ent.start_rest(RestKind::Long).unwrap();
+        ent.exert(30);
+        ent.finish_rest().unwrap();
+
+        assert_eq!(ent.hp(), ent.max_hp());
+        assert_eq!(ent.uses(lucky.id()), Some((Lucky::POINTS, Lucky::POINTS)));
+        assert_eq!(ent.exhaustion(), 1);
+
+        // Half of 3 hit dice (rounded down), biggest first.
+        assert_eq!(ent.hit_dice(), [(10, 1), (6, 0)]);
+    }
+}
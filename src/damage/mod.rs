@@ -0,0 +1,109 @@
+//!
+//! Damage, and the types thereof.
+//!
+
+use crate::identity::Identity;
+
+///
+/// The kinds of damage under 5E rules.
+///
+/// Resistances, vulnerabilities and immunities
+/// are all expressed in terms of these.
+///
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
+pub enum DamageType {
+    Acid,
+    Bludgeoning,
+    Cold,
+    Fire,
+    Force,
+    Lightning,
+    Necrotic,
+    Piercing,
+    Poison,
+    Psychic,
+    Radiant,
+    Slashing,
+    Thunder,
+}
+
+impl Identity for DamageType {
+    fn id(&self) -> &'static str {
+        use DamageType::*;
+
+        match self {
+            Acid => "5E::DAMAGE::ACID",
+            Bludgeoning => "5E::DAMAGE::BLUDGEONING",
+            Cold => "5E::DAMAGE::COLD",
+            Fire => "5E::DAMAGE::FIRE",
+            Force => "5E::DAMAGE::FORCE",
+            Lightning => "5E::DAMAGE::LIGHTNING",
+            Necrotic => "5E::DAMAGE::NECROTIC",
+            Piercing => "5E::DAMAGE::PIERCING",
+            Poison => "5E::DAMAGE::POISON",
+            Psychic => "5E::DAMAGE::PSYCHIC",
+            Radiant => "5E::DAMAGE::RADIANT",
+            Slashing => "5E::DAMAGE::SLASHING",
+            Thunder => "5E::DAMAGE::THUNDER",
+        }
+    }
+
+    fn __id() -> &'static str
+    where
+        Self: Sized,
+    {
+        "5E::DAMAGE"
+    }
+}
+
+///
+/// Damage types a creature resists, ignores, or takes double from.
+///
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Defenses {
+    resistances: Vec<DamageType>,
+    immunities: Vec<DamageType>,
+    vulnerabilities: Vec<DamageType>,
+}
+
+impl Defenses {
+    pub fn resist(&mut self, kind: DamageType) -> &mut Self {
+        self.resistances.push(kind);
+        self
+    }
+
+    pub fn immune(&mut self, kind: DamageType) -> &mut Self {
+        self.immunities.push(kind);
+        self
+    }
+
+    pub fn vulnerable(&mut self, kind: DamageType) -> &mut Self {
+        self.vulnerabilities.push(kind);
+        self
+    }
+
+    pub fn resistances(&self) -> &[DamageType] {
+        &self.resistances
+    }
+
+    pub fn immunities(&self) -> &[DamageType] {
+        &self.immunities
+    }
+
+    pub fn vulnerabilities(&self) -> &[DamageType] {
+        &self.vulnerabilities
+    }
+
+    ///
+    /// Damage actually taken, of a type:
+    /// resistance halves it (rounded down).
+    ///
+    pub fn damage_taken(&self, amount: usize, kind: DamageType) -> usize {
+        match kind {
+            k if self.immunities.contains(&k) => 0,
+            k if self.resistances.contains(&k) => amount / 2,
+            k if self.vulnerabilities.contains(&k) => amount * 2,
+            _ => amount,
+        }
+    }
+}
@@ -29,9 +29,12 @@
 pub mod modifiers;
 mod rolls;
 
-use std::ops::{Add, Div, Mul, Sub};
+use std::{
+    cell::RefCell,
+    ops::{Add, Div, Mul, Sub},
+};
 
-use rand::Rng;
+use rand::{rngs::StdRng, Rng, SeedableRng};
 use xander_macros::dice;
 
 pub use rolls::{Roll, Rolls};
@@ -62,14 +65,12 @@ pub trait Die :
     /// Roll this die `n` times.
     /// 
     fn roll(&self, times : usize) -> Rolls {
-        let mut rng = rand::thread_rng();
-
         let mut r = Rolls::default();
         Rolls::add(
             &mut r,
             self,
             (0..times)
-                .map(move |_| rng.gen_range(1..=self.sides()) as i32)
+                .map(|_| random(self.sides()) as i32)
                 .map(Roll::from)
         );
 
@@ -77,6 +78,60 @@ pub trait Die :
     }
 }
 
+thread_local! {
+    static SEEDED: RefCell<Option<StdRng>> = const { RefCell::new(None) };
+}
+
+///
+/// Make every roll on this thread reproducible,
+/// until [unseed] is called.
+///
+pub fn seed(seed: u64) {
+    SEEDED.with(|rng| *rng.borrow_mut() = Some(StdRng::seed_from_u64(seed)));
+}
+
+///
+/// Go back to unpredictable rolls on this thread.
+///
+pub fn unseed() {
+    SEEDED.with(|rng| *rng.borrow_mut() = None);
+}
+
+///
+/// Seeds every roll on this thread while held (see [seeded]),
+/// putting back the previous seed, if any, when dropped.
+///
+#[derive(Debug)]
+#[must_use]
+pub struct Seeded {
+    previous: Option<StdRng>,
+}
+
+///
+/// Make every roll on this thread reproducible,
+/// until the returned guard is dropped.
+///
+pub fn seeded(seed: u64) -> Seeded {
+    let previous = SEEDED.with(|rng| rng.borrow_mut().replace(StdRng::seed_from_u64(seed)));
+    Seeded { previous }
+}
+
+impl Drop for Seeded {
+    fn drop(&mut self) {
+        SEEDED.with(|rng| *rng.borrow_mut() = self.previous.take());
+    }
+}
+
+///
+/// A number from 1 to `sides`.
+///
+fn random(sides: usize) -> usize {
+    SEEDED.with(|rng| match rng.borrow_mut().as_mut() {
+        Some(rng) => rng.gen_range(1..=sides),
+        None => rand::thread_rng().gen_range(1..=sides),
+    })
+}
+
 dice!(4, 6, 8, 10, 12, 20, 100);
 
 ///
@@ -194,4 +249,35 @@ mod tests {
         let results = D20() + 23;
         println!("{results:?}")
     }
+
+    #[test]
+    fn seeding() {
+        let rolls = |seed| {
+            super::seed(seed);
+            let rolls = (0..10).map(|_| D20().total()).collect::<Vec<_>>();
+            super::unseed();
+            rolls
+        };
+
+        assert_eq!(rolls(7), rolls(7));
+        assert_ne!(rolls(7), rolls(8));
+    }
+
+    #[test]
+    fn seeded() {
+        let rolls = || (0..10).map(|_| D20().total()).collect::<Vec<_>>();
+
+        super::seed(7);
+        let expected = [rolls(), rolls()];
+
+        super::seed(7);
+        let first = rolls();
+        {
+            let _seeded = super::seeded(8);
+            assert_ne!(rolls(), expected[1]);
+        }
+        // Picks up where it left off.
+        assert_eq!([first, rolls()], expected);
+        super::unseed();
+    }
 }
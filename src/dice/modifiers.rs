@@ -116,6 +116,28 @@ impl Modifier for Arithmetic {
             Div(p) => Some(subtotal / p),
         }
     }
+
+    fn fold(&self, total: i32) -> i32 {
+        use Arithmetic::*;
+
+        match self {
+            Add(p) => total + p,
+            Sub(p) => total - p,
+            Mul(p) => total * p,
+            Div(p) => total / p,
+        }
+    }
+
+    fn expect(&self, total: f32) -> f32 {
+        use Arithmetic::*;
+
+        match self {
+            Add(p) => total + *p as f32,
+            Sub(p) => total - *p as f32,
+            Mul(p) => total * *p as f32,
+            Div(p) => total / *p as f32,
+        }
+    }
 }
 
 ///
@@ -147,6 +169,23 @@ pub trait Modifier: std::fmt::Debug {
     /// in place.
     ///
     fn apply(&self, raw_rolls: Vec<(&usize, &mut Vec<RollType>)>) -> Option<i32>;
+
+    ///
+    /// Apply this operation on top of a running total,
+    /// so that arithmetic modifiers can be chained.
+    ///
+    /// Non-arithmetic modifiers leave the total untouched.
+    ///
+    fn fold(&self, total: i32) -> i32 {
+        total
+    }
+
+    ///
+    /// [Modifier::fold], for an expected (fractional) total.
+    ///
+    fn expect(&self, total: f32) -> f32 {
+        total
+    }
 }
 
 #[derive(Debug, Clone, Copy)]
@@ -173,17 +212,54 @@ impl<D: Die + std::fmt::Debug + Copy> Modifier for Advantage<D> {
     ) -> Option<i32> {
         raw_rolls
             .into_iter()
-            .for_each(|(sides, rolls)| match *sides == self.0.sides() {
-                true => {
-                    rolls.iter_mut().for_each(Roll::hide);
-
-                    rolls
-                        .iter_mut()
-                        .max_by_key(|a| a.value())
-                        .map(Roll::show)
-                        .unwrap_or(());
-                }
-                false => rolls.iter_mut().for_each(Roll::hide),
+            .filter(|(sides, _)| **sides == self.0.sides())
+            .for_each(|(_, rolls)| {
+                rolls.iter_mut().for_each(Roll::hide);
+
+                rolls
+                    .iter_mut()
+                    .max_by(|a, b| a.partial_cmp(b).unwrap())
+                    .map(Roll::show)
+                    .unwrap_or(());
+            });
+
+        None
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct Disadvantage<D: Die + std::fmt::Debug + Copy>(pub D);
+
+impl<D: Die + std::fmt::Debug + Copy + 'static> IntoModifier for Disadvantage<D> {
+    fn into_modifier(&self) -> Box<dyn Modifier> {
+        Box::new(*self)
+    }
+}
+
+impl<D: Die + std::fmt::Debug + Copy> Modifier for Disadvantage<D> {
+    fn id(&self) -> &'static str {
+        "5E::DISADVANTAGE"
+    }
+
+    fn symbol(&self) -> Option<&'static str> {
+        None
+    }
+
+    fn apply(
+        &self,
+        raw_rolls: Vec<(&usize, &mut Vec<crate::dice::rolls::RollType>)>,
+    ) -> Option<i32> {
+        raw_rolls
+            .into_iter()
+            .filter(|(sides, _)| **sides == self.0.sides())
+            .for_each(|(_, rolls)| {
+                rolls.iter_mut().for_each(Roll::hide);
+
+                rolls
+                    .iter_mut()
+                    .min_by(|a, b| a.partial_cmp(b).unwrap())
+                    .map(Roll::show)
+                    .unwrap_or(());
             });
 
         None
@@ -192,9 +268,43 @@ impl<D: Die + std::fmt::Debug + Copy> Modifier for Advantage<D> {
 
 #[cfg(test)]
 mod tests {
-    use crate::dice::D20;
+    use crate::dice::{Roll, Rolls, D20, D4};
+
+    use super::{Advantage, Disadvantage};
 
-    use super::Advantage;
+    #[test]
+    fn advantage_keeps_highest() {
+        let mut rolls = Rolls::default();
+        rolls.add(&D20, [3, 17].map(Roll::from));
+
+        assert_eq!(rolls.then(Advantage(D20)).total(), 17);
+    }
+
+    #[test]
+    fn advantage_keeps_other_dice() {
+        let mut rolls = Rolls::default();
+        rolls.add(&D20, [3, 17].map(Roll::from));
+        rolls.add(&D4, [2].map(Roll::from));
+
+        // The d4 is not part of the advantage roll and still counts.
+        assert_eq!(rolls.then(Advantage(D20)).total(), 19);
+    }
+
+    #[test]
+    fn disadvantage_keeps_lowest() {
+        let mut rolls = Rolls::default();
+        rolls.add(&D20, [3, 17].map(Roll::from));
+
+        assert_eq!(rolls.then(Disadvantage(D20)).total(), 3);
+    }
+
+    #[test]
+    fn chained_arithmetic() {
+        let mut rolls = Rolls::default();
+        rolls.add(&D20, [10].map(Roll::from));
+
+        assert_eq!((rolls + 2 + 3 - 1).total(), 14);
+    }
 
     #[test]
     fn advantage() {
@@ -23,13 +23,19 @@ pub struct Roll {
     /// Hidden roles are not accounted for in totals.
     ///
     hidden: bool,
+
+    ///
+    /// Negated rolls are subtracted from totals (e.g. Bane).
+    ///
+    negated: bool,
 }
 
 impl std::fmt::Debug for Roll {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(
             f,
-            "Roll({})",
+            "Roll({}{})",
+            if self.negated { "-" } else { "" },
             &self
                 .hidden
                 .then(|| "_".to_string())
@@ -55,6 +61,7 @@ impl From<RollInner> for Roll {
         Self {
             value,
             hidden: false,
+            negated: false,
         }
     }
 }
@@ -72,8 +79,22 @@ impl Roll {
         self.hidden
     }
 
+    pub fn negate(&mut self) {
+        self.negated = !self.negated;
+    }
+
+    pub fn negated(&self) -> bool {
+        self.negated
+    }
+
     pub fn value(&self) -> RollInner {
-        self.hidden.then_some(self.value).unwrap_or(0)
+        if self.hidden {
+            0
+        } else if self.negated {
+            -self.value
+        } else {
+            self.value
+        }
     }
 }
 
@@ -115,6 +136,21 @@ impl Rolls {
         self
     }
 
+    ///
+    /// Replaces all rolls associated with a die
+    /// (e.g. a d20 swapped for a Portent roll).
+    ///
+    pub fn replace(
+        mut self,
+        die: &(impl Die + ?Sized),
+        values: impl IntoIterator<Item = RollType>,
+    ) -> Self {
+        self.raw_rolls
+            .insert(die.sides(), values.into_iter().collect());
+
+        self
+    }
+
     ///
     /// Add a modifier.
     ///
@@ -123,23 +159,107 @@ impl Rolls {
         self
     }
 
+    ///
+    /// Add an already-boxed modifier.
+    ///
+    pub fn modify(mut self, modifier: Box<dyn Modifier>) -> Self {
+        self.modifiers.push(modifier);
+        self
+    }
+
+    ///
+    /// Run `modifiers` over `raw`: modifiers on the dice
+    /// themselves (advantage, rerolls...) go first, arithmetic
+    /// is then folded over the subtotal.
+    ///
+    /// Also returns whether any modifier worked out a sum.
+    ///
+    fn resolve(
+        modifiers: &[Box<dyn Modifier>],
+        raw: &mut HashMap<usize, Vec<RollType>>,
+    ) -> (i32, bool) {
+        let mut subtotal = None;
+        for modifier in modifiers.iter().filter(|m| !m.is_arithmetic()) {
+            let v = raw.iter_mut().collect();
+            if let Some(s) = modifier.apply(v) {
+                subtotal = Some(s);
+            }
+        }
+
+        let arithmetic = modifiers.iter().filter(|m| m.is_arithmetic());
+        let summed = subtotal.is_some() || arithmetic.clone().next().is_some();
+
+        let subtotal = subtotal.unwrap_or_else(|| {
+            raw.values()
+                .map(|v| v.iter().map(RollType::value).sum::<i32>())
+                .sum::<i32>()
+        });
+
+        let total = arithmetic.fold(subtotal, |total, modifier| modifier.fold(total));
+        (total, summed)
+    }
+
     ///
     /// Peek at what the total is,
     /// without needing ownership of `self`.
     ///
     pub fn peek(&self) -> i32 {
+        Self::resolve(&self.modifiers, &mut self.raw_rolls.clone()).0
+    }
+
+    ///
+    /// Sum of the dice alone, without any modifiers
+    /// (e.g. the extra dice of a critical hit).
+    ///
+    pub fn dice_total(&self) -> i32 {
+        self.raw_rolls
+            .values()
+            .map(|v| v.iter().map(RollType::value).sum::<i32>())
+            .sum()
+    }
+
+    ///
+    /// Values of `die` still counting once the dice
+    /// modifiers have run (e.g. the d20 kept with advantage).
+    ///
+    pub fn kept(&self, die: &(impl Die + ?Sized)) -> Vec<RollInner> {
         let mut raw = self.raw_rolls.clone();
-        for modifier in self.modifiers.iter() {
-            let v = raw.iter_mut().collect();
-            match modifier.apply(v) {
-                Some(s) => return s,
-                None => {}
-            }
-        }
+        Self::resolve(&self.modifiers, &mut raw);
+
+        raw.remove(&die.sides())
+            .unwrap_or_default()
+            .iter()
+            .filter(|r| !r.hidden())
+            .map(RollType::value)
+            .collect()
+    }
 
-        raw.into_iter()
-            .map(|(_, v)| v.iter().map(RollType::value).sum::<i32>())
-            .sum::<i32>()
+    ///
+    /// Average total of these dice: each die counts
+    /// for half its sides plus one (minus, if negated),
+    /// before arithmetic.
+    ///
+    /// Dice modifiers (advantage, rerolls...) are ignored.
+    ///
+    pub fn expected(&self) -> f32 {
+        let subtotal = self
+            .raw_rolls
+            .iter()
+            .flat_map(|(sides, rolls)| rolls.iter().map(move |r| (sides, r)))
+            .map(|(sides, r)| {
+                let average = (*sides as f32 + 1.0) / 2.0;
+                if r.negated() {
+                    -average
+                } else {
+                    average
+                }
+            })
+            .sum::<f32>();
+
+        self.modifiers
+            .iter()
+            .filter(|m| m.is_arithmetic())
+            .fold(subtotal, |total, modifier| modifier.expect(total))
     }
 
     ///
@@ -148,15 +268,10 @@ impl Rolls {
     ///     depending on the presence of arithmetic methods.
     ///
     pub fn apply(mut self) -> Result<i32, Self> {
-        for modifier in self.modifiers.iter() {
-            let v = self.raw_rolls.iter_mut().collect();
-            match modifier.apply(v) {
-                Some(s) => return Ok(s),
-                None => {}
-            }
+        match Self::resolve(&self.modifiers, &mut self.raw_rolls) {
+            (total, true) => Ok(total),
+            _ => Err(self),
         }
-
-        Err(self)
     }
 
     ///
@@ -181,7 +296,9 @@ impl Rolls {
     /// Adds all the rolls from the other [Rolls] collection.
     ///
     pub fn extend(mut self, other: Self) -> Self {
-        self.raw_rolls.extend(other.raw_rolls.into_iter());
+        for (sides, rolls) in other.raw_rolls {
+            self.raw_rolls.entry(sides).or_default().extend(rolls);
+        }
 
         self.modifiers.extend(other.modifiers);
 
@@ -231,6 +348,25 @@ impl Add<Rolls> for Rolls {
     }
 }
 
+///
+/// Subtracts the dice of the other [Rolls] collection,
+/// keeping them as negated rolls (e.g. Bane's d4).
+///
+/// Modifiers on `rhs` are not carried over.
+///
+impl Sub<Rolls> for Rolls {
+    type Output = Self;
+
+    fn sub(mut self, rhs: Rolls) -> Self::Output {
+        for (sides, mut rolls) in rhs.raw_rolls {
+            rolls.iter_mut().for_each(Roll::negate);
+            self.raw_rolls.entry(sides).or_default().extend(rolls);
+        }
+
+        self
+    }
+}
+
 impl<D: Die> Index<D> for Rolls {
     type Output = [Roll];
 
@@ -245,6 +381,7 @@ impl<D: Die> Index<D> for Rolls {
 #[cfg(test)]
 mod tests {
     use crate::dice::{
+        modifiers::Advantage,
         rolls::{Roll, RollType},
         D20, D4,
     };
@@ -286,4 +423,74 @@ mod tests {
         let results = D20(12) + D4(13);
         assert_eq!(13, results[D4].len())
     }
+
+    #[test]
+    fn expected() {
+        assert_eq!((D20(1) + 2).expected(), 12.5);
+        assert_eq!(((D4(2) + D20(1)) * 2).expected(), 31.0);
+
+        let rolls = D4(3) + 10;
+        assert_eq!(rolls.dice_total() + 10, rolls.peek());
+    }
+
+    #[test]
+    fn extend_same_die() {
+        let results = D4(2) + D4(3);
+        assert_eq!(5, results[D4].len())
+    }
+
+    #[test]
+    fn hidden() {
+        let mut roll = Roll::from(7);
+        assert_eq!(roll.value(), 7);
+
+        roll.hide();
+        assert_eq!(roll.value(), 0);
+    }
+
+    #[test]
+    fn apply() {
+        let mut rolls = Rolls::default();
+        rolls.add(&D20, [3, 17].map(Roll::from));
+
+        let total = rolls.then(Advantage(D20)).then(|x| x + 1).then(|x| x * 2);
+        assert_eq!(total.apply().ok(), Some(36));
+
+        assert!(D20(1).apply().is_err());
+    }
+
+    #[test]
+    fn modifier_order() {
+        let mut rolls = Rolls::default();
+        rolls.add(&D20, [3, 17].map(Roll::from));
+
+        // Advantage applies to the dice even when added after the arithmetic.
+        let total = rolls.then(|x| x + 1).then(Advantage(D20));
+        assert_eq!(total.apply().ok(), Some(18));
+    }
+
+    #[test]
+    fn kept() {
+        let mut rolls = Rolls::default();
+        rolls.add(&D20, [3, 17].map(Roll::from));
+
+        let rolls = rolls.then(Advantage(D20)).then(|x| x + 1);
+        assert_eq!(rolls.kept(&D20), [17]);
+        assert_eq!(rolls[D20].len(), 2);
+        assert!(rolls.kept(&D4).is_empty());
+    }
+
+    #[test]
+    fn negated() {
+        let mut penalty = Rolls::default();
+        penalty.add(&D4, [3].map(Roll::from));
+
+        let mut rolls = Rolls::default();
+        rolls.add(&D20, [12].map(Roll::from));
+
+        let rolls = (rolls - penalty) + 2;
+        assert!(rolls[D4][0].negated());
+        assert_eq!(rolls.expected(), 10.5 - 2.5 + 2.0);
+        assert_eq!(rolls.total(), 11);
+    }
 }
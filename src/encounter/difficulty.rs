@@ -0,0 +1,263 @@
+use crate::{class::MAX_LEVEL, creature::Creature, monster::Monster};
+
+///
+/// How tough an encounter is for a party.
+///
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Difficulty {
+    ///
+    /// Below the easy threshold.
+    ///
+    Trivial,
+    Easy,
+    Medium,
+    Hard,
+    Deadly,
+}
+
+///
+/// Easy, medium, hard and deadly XP thresholds per character level.
+///
+pub const THRESHOLDS: [[usize; 4]; MAX_LEVEL] = [
+    [25, 50, 75, 100],
+    [50, 100, 150, 200],
+    [75, 150, 225, 400],
+    [125, 250, 375, 500],
+    [250, 500, 750, 1_100],
+    [300, 600, 900, 1_400],
+    [350, 750, 1_100, 1_700],
+    [450, 900, 1_400, 2_100],
+    [550, 1_100, 1_600, 2_400],
+    [600, 1_200, 1_900, 2_800],
+    [800, 1_600, 2_400, 3_600],
+    [1_000, 2_000, 3_000, 4_500],
+    [1_100, 2_200, 3_400, 5_100],
+    [1_250, 2_500, 3_800, 5_700],
+    [1_400, 2_800, 4_300, 6_400],
+    [1_600, 3_200, 4_800, 7_200],
+    [2_000, 3_900, 5_900, 8_800],
+    [2_100, 4_200, 6_300, 9_500],
+    [2_400, 4_900, 7_300, 10_900],
+    [2_800, 5_700, 8_500, 12_700],
+];
+
+///
+/// Adjusted XP a character can handle in a day of adventuring, per level.
+///
+pub const DAILY: [usize; MAX_LEVEL] = [
+    300, 600, 1_200, 1_700, 3_500, 4_000, 5_000, 6_000, 7_500, 9_000, 10_500, 11_500, 13_500,
+    15_000, 18_000, 20_000, 25_000, 27_000, 30_000, 40_000,
+];
+
+///
+/// Encounter multipliers, from a lone monster against a
+/// large party up to fifteen or more against a small one.
+///
+const MULTIPLIERS: [f32; 8] = [0.5, 1.0, 1.5, 2.0, 2.5, 3.0, 4.0, 5.0];
+
+fn row(level: usize) -> &'static [usize; 4] {
+    &THRESHOLDS[level.clamp(1, MAX_LEVEL) - 1]
+}
+
+///
+/// Easy, medium, hard and deadly thresholds of a party, by level.
+///
+pub fn thresholds(levels: &[usize]) -> [usize; 4] {
+    levels.iter().fold([0; 4], |mut sum, level| {
+        sum.iter_mut().zip(row(*level)).for_each(|(s, t)| *s += t);
+        sum
+    })
+}
+
+///
+/// XP multiplier for `monsters` fighting a party of `party`:
+/// small parties (fewer than 3) count one step higher,
+/// and large ones (6 or more) one step lower.
+///
+pub fn multiplier(monsters: usize, party: usize) -> f32 {
+    let step = match monsters {
+        0 => return 0.0,
+        1 => 1,
+        2 => 2,
+        3..=6 => 3,
+        7..=10 => 4,
+        11..=14 => 5,
+        _ => 6,
+    };
+
+    let step = match party {
+        0..=2 => step + 1,
+        6.. => step - 1,
+        _ => step,
+    };
+
+    MULTIPLIERS[step]
+}
+
+///
+/// Difficulty of an encounter, and how it was rated.
+///
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Rating {
+    ///
+    /// XP the monsters are worth, to be split on victory.
+    ///
+    pub xp: usize,
+    pub multiplier: f32,
+    ///
+    /// XP scaled by the number of monsters: what difficulty
+    /// (and the daily budget) goes by.
+    ///
+    pub adjusted: usize,
+    pub thresholds: [usize; 4],
+    pub difficulty: Difficulty,
+}
+
+impl Rating {
+    ///
+    /// Rate monsters worth `xp` each against a party of `levels`.
+    ///
+    pub fn new(levels: &[usize], xp: &[usize]) -> Self {
+        let total = xp.iter().sum::<usize>();
+        let multiplier = multiplier(xp.len(), levels.len());
+        let adjusted = (total as f32 * multiplier) as usize;
+        let thresholds = thresholds(levels);
+
+        let difficulty = match thresholds.iter().filter(|t| adjusted >= **t).count() {
+            0 => Difficulty::Trivial,
+            1 => Difficulty::Easy,
+            2 => Difficulty::Medium,
+            3 => Difficulty::Hard,
+            _ => Difficulty::Deadly,
+        };
+
+        Self {
+            xp: total,
+            multiplier,
+            adjusted,
+            thresholds,
+            difficulty,
+        }
+    }
+}
+
+///
+/// Rate `monsters` against `party`.
+///
+pub fn rate(party: &[&Creature], monsters: &[&Monster]) -> Rating {
+    let levels = party.iter().map(|pc| pc.level()).collect::<Vec<_>>();
+    let xp = monsters.iter().map(|m| m.xp()).collect::<Vec<_>>();
+    Rating::new(&levels, &xp)
+}
+
+///
+/// Encounters run over an adventuring day,
+/// against the party's daily XP budget.
+///
+#[derive(Debug, Clone, PartialEq)]
+pub struct Day {
+    budget: usize,
+    encounters: Vec<Rating>,
+}
+
+impl Day {
+    pub fn new(levels: &[usize]) -> Self {
+        Self {
+            budget: levels
+                .iter()
+                .map(|l| DAILY[(*l).clamp(1, MAX_LEVEL) - 1])
+                .sum(),
+            encounters: vec![],
+        }
+    }
+
+    pub fn of(party: &[&Creature]) -> Self {
+        Self::new(&party.iter().map(|pc| pc.level()).collect::<Vec<_>>())
+    }
+
+    ///
+    /// Run an encounter, returning the budget left.
+    ///
+    pub fn add(&mut self, rating: Rating) -> usize {
+        self.encounters.push(rating);
+        self.remaining()
+    }
+
+    pub fn budget(&self) -> usize {
+        self.budget
+    }
+
+    ///
+    /// Adjusted XP of every encounter so far.
+    ///
+    pub fn spent(&self) -> usize {
+        self.encounters.iter().map(|r| r.adjusted).sum()
+    }
+
+    pub fn remaining(&self) -> usize {
+        self.budget.saturating_sub(self.spent())
+    }
+
+    ///
+    /// Has the party had as much as it can take today?
+    ///
+    pub fn exhausted(&self) -> bool {
+        self.spent() >= self.budget
+    }
+
+    pub fn encounters(&self) -> &[Rating] {
+        &self.encounters
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        creature::Creature,
+        monster::{self, Cr},
+    };
+
+    use super::{multiplier, rate, thresholds, Day, Difficulty, Rating};
+
+    #[test]
+    fn ratings() {
+        assert_eq!(thresholds(&[3, 3, 3, 2]), [275, 550, 825, 1_400]);
+        assert_eq!(multiplier(1, 4), 1.0);
+        assert_eq!(multiplier(4, 4), 2.0);
+        assert_eq!(multiplier(1, 2), 1.5);
+        assert_eq!(multiplier(20, 1), 5.0);
+        assert_eq!(multiplier(1, 6), 0.5);
+
+        // Four goblins count double.
+        let goblins = Rating::new(&[3, 3, 3, 2], &[Cr::Quarter.xp(); 4]);
+        assert_eq!((goblins.xp, goblins.adjusted), (200, 400));
+        assert_eq!(goblins.difficulty, Difficulty::Easy);
+
+        let ogre = Rating::new(&[1, 1, 1, 1], &[Cr::Whole(2).xp()]);
+        assert_eq!(ogre.difficulty, Difficulty::Deadly);
+        assert_eq!(Rating::new(&[5; 4], &[]).difficulty, Difficulty::Trivial);
+
+        let fighter = Creature::default();
+        let goblin = monster::goblin();
+        let rating = rate(&[&fighter], &[&goblin, &goblin]);
+        assert_eq!((rating.multiplier, rating.adjusted), (2.0, 200));
+        assert_eq!(rating.difficulty, Difficulty::Deadly);
+    }
+
+    #[test]
+    fn adventuring_day() {
+        let mut day = Day::new(&[3; 4]);
+        assert_eq!(day.budget(), 4_800);
+
+        let goblins = Rating::new(&[3; 4], &[Cr::Quarter.xp(); 6]);
+        assert_eq!(day.add(goblins), 4_200);
+        assert_eq!(day.add(goblins), 3_600);
+        assert!(!day.exhausted());
+
+        let dragon = Rating::new(&[3; 4], &[Cr::Whole(17).xp()]);
+        assert_eq!(day.add(dragon), 0);
+        assert!(day.exhausted());
+        assert_eq!(day.encounters().len(), 3);
+        assert_eq!(day.spent(), 19_200);
+    }
+}
@@ -0,0 +1,487 @@
+//!
+//! Encounters: initiative, rounds and turns, and how difficult they are.
+//!
+//! ### Examples
+//! ***
+//! Running a fight between a fighter and two goblins:
+//! ```
+//! use xander::{creature::Creature, encounter::Encounter};
+//!
+//! let mut encounter = Encounter::default();
+//! let fighter = encounter.add(Creature::default());
+//! let goblins = encounter.add_group("GOBLIN", [Creature::default(), Creature::default()]);
+//!
+//! encounter.roll_initiative();
+//!
+//! // Goblins share one initiative roll, so they act back to back.
+//! let first = encounter.next_turn().unwrap();
+//! assert_eq!(first.round, 1);
+//!
+//! let order = encounter.order();
+//! assert!(order == [fighter, goblins[0], goblins[1]] || order == [goblins[0], goblins[1], fighter]);
+//! ```
+//!
+
+mod difficulty;
+mod simulation;
+
+pub use difficulty::{multiplier, rate, thresholds, Day, Difficulty, Rating, DAILY, THRESHOLDS};
+pub use simulation::{Outcome, Policy, Report, Setup, Simulation, Unit};
+
+use std::collections::HashMap;
+
+use crate::{
+    ability::Dexterity,
+    creature::{context::CheckContext, experience, Creature},
+};
+
+///
+/// Tag on the [CheckContext] of initiative rolls,
+/// for [crate::creature::context::Situational] modifiers
+/// and [crate::creature::context::Edge]s to look for.
+///
+pub const INITIATIVE: &str = "5E::TAG::INITIATIVE";
+
+///
+/// Handle to a creature taking part in an [Encounter].
+///
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub struct CombatantId(usize);
+
+#[derive(Debug)]
+pub struct Combatant {
+    creature: Creature,
+    group: Option<&'static str>,
+    initiative: Option<i32>,
+}
+
+impl Combatant {
+    pub fn creature(&self) -> &Creature {
+        &self.creature
+    }
+
+    pub fn creature_mut(&mut self) -> &mut Creature {
+        &mut self.creature
+    }
+
+    ///
+    /// Group of identical monsters this is part of, if any.
+    ///
+    pub fn group(&self) -> Option<&'static str> {
+        self.group
+    }
+
+    ///
+    /// Initiative roll, once rolled.
+    ///
+    pub fn initiative(&self) -> Option<i32> {
+        self.initiative
+    }
+}
+
+///
+/// Whose turn it is, and in which round.
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Turn {
+    pub round: usize,
+    pub combatant: CombatantId,
+}
+
+///
+/// Called at the start or end of every turn,
+/// with the creature whose turn it is.
+///
+pub type TurnHook = dyn FnMut(Turn, &mut Creature);
+
+#[derive(Default)]
+pub struct Encounter {
+    combatants: Vec<Combatant>,
+    order: Vec<CombatantId>,
+    round: usize,
+    index: Option<usize>,
+    start_of_turn: Vec<Box<TurnHook>>,
+    end_of_turn: Vec<Box<TurnHook>>,
+}
+
+impl Encounter {
+    ///
+    /// Add a creature to the fight.
+    ///
+    /// It only joins the turn order on the
+    /// next [Encounter::roll_initiative].
+    ///
+    pub fn add(&mut self, creature: Creature) -> CombatantId {
+        self.join(creature, None)
+    }
+
+    ///
+    /// Add a group of identical monsters, which share
+    /// a single initiative roll (DMG p. 189).
+    ///
+    pub fn add_group(
+        &mut self,
+        group: &'static str,
+        creatures: impl IntoIterator<Item = Creature>,
+    ) -> Vec<CombatantId> {
+        creatures
+            .into_iter()
+            .map(|c| self.join(c, Some(group)))
+            .collect()
+    }
+
+    fn join(&mut self, creature: Creature, group: Option<&'static str>) -> CombatantId {
+        self.combatants.push(Combatant {
+            creature,
+            group,
+            initiative: None,
+        });
+
+        CombatantId(self.combatants.len() - 1)
+    }
+
+    pub fn combatant(&self, id: CombatantId) -> &Combatant {
+        &self.combatants[id.0]
+    }
+
+    pub fn combatant_mut(&mut self, id: CombatantId) -> &mut Combatant {
+        &mut self.combatants[id.0]
+    }
+
+    pub fn combatants(&self) -> impl Iterator<Item = (CombatantId, &Combatant)> {
+        self.combatants
+            .iter()
+            .enumerate()
+            .map(|(i, c)| (CombatantId(i), c))
+    }
+
+    ///
+    /// Roll initiative (a DEX check) for everyone,
+    /// and start again from the top of round one.
+    ///
+    /// Ties go to the higher DEX score, then
+    /// to whoever joined the encounter first.
+    ///
+    pub fn roll_initiative(&mut self) {
+        let mut groups = HashMap::<&'static str, (usize, i32)>::new();
+        let mut keys = Vec::with_capacity(self.combatants.len());
+
+        for (i, c) in self.combatants.iter_mut().enumerate() {
+            let roll = || {
+                c.creature
+                    .check_in(Dexterity, CheckContext::default().tag(INITIATIVE))
+                    .total()
+            };
+
+            let (leader, initiative) = match c.group {
+                Some(group) => *groups.entry(group).or_insert_with(|| (i, roll())),
+                None => (i, roll()),
+            };
+
+            c.initiative = Some(initiative);
+            keys.push((
+                std::cmp::Reverse(initiative),
+                std::cmp::Reverse(c.creature.score::<Dexterity>(Dexterity).unwrap_or(0)),
+                leader,
+            ));
+        }
+
+        self.order = (0..self.combatants.len()).map(CombatantId).collect();
+        self.order.sort_by_key(|id| keys[id.0]);
+        self.round = 0;
+        self.index = None;
+    }
+
+    ///
+    /// Turn order, highest initiative first.
+    ///
+    pub fn order(&self) -> &[CombatantId] {
+        &self.order
+    }
+
+    ///
+    /// Current round, `0` before the first turn.
+    ///
+    pub fn round(&self) -> usize {
+        self.round
+    }
+
+    pub fn current(&self) -> Option<Turn> {
+        self.index.map(|i| Turn {
+            round: self.round,
+            combatant: self.order[i],
+        })
+    }
+
+    ///
+    /// End the current turn, and start the next one,
+    /// moving on to a new round when everyone has acted.
+    ///
+    /// Refreshes the next creature's action economy
+    /// (see [Creature::start_turn]), before any hooks run.
+    ///
+    /// Returns `None` if initiative hasn't been rolled.
+    ///
+    pub fn next_turn(&mut self) -> Option<Turn> {
+        if self.order.is_empty() {
+            return None;
+        }
+
+        if let Some(turn) = self.current() {
+            let creature = &mut self.combatants[turn.combatant.0].creature;
+            self.end_of_turn
+                .iter_mut()
+                .for_each(|hook| hook(turn, creature));
+            creature.end_turn();
+        }
+
+        let index = match self.index {
+            Some(i) if i + 1 < self.order.len() => i + 1,
+            _ => {
+                self.round += 1;
+                0
+            }
+        };
+        self.index = Some(index);
+
+        let turn = self.current()?;
+        let creature = &mut self.combatants[turn.combatant.0].creature;
+        creature.start_turn();
+        self.start_of_turn
+            .iter_mut()
+            .for_each(|hook| hook(turn, creature));
+
+        Some(turn)
+    }
+
+    pub fn on_start_of_turn(
+        &mut self,
+        hook: impl FnMut(Turn, &mut Creature) + 'static,
+    ) -> &mut Self {
+        self.start_of_turn.push(Box::new(hook));
+        self
+    }
+
+    pub fn on_end_of_turn(&mut self, hook: impl FnMut(Turn, &mut Creature) + 'static) -> &mut Self {
+        self.end_of_turn.push(Box::new(hook));
+        self
+    }
+
+    ///
+    /// Split `xp` evenly across `party`,
+    /// returning each member's share.
+    ///
+    pub fn award(&mut self, party: &[CombatantId], xp: usize) -> usize {
+        let share = experience::split(xp, party.len());
+        for id in party {
+            self.combatant_mut(*id).creature_mut().gain_xp(share);
+        }
+        share
+    }
+}
+
+impl std::fmt::Debug for Encounter {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Encounter")
+            .field("combatants", &self.combatants)
+            .field("order", &self.order)
+            .field("round", &self.round)
+            .field("index", &self.index)
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{cell::RefCell, rc::Rc};
+
+    use crate::{
+        ability::Dexterity,
+        creature::{
+            actions::{ActionError, ActionKind},
+            context::{Edge, Situational},
+            experience::Advancement,
+            Creature,
+        },
+        dice::{modifiers::Arithmetic, D20},
+        identity::Identity,
+    };
+
+    use super::{Encounter, Turn, INITIATIVE};
+
+    fn with_dex(score: usize) -> Creature {
+        let mut c = Creature::default();
+        c.stats().insert(Dexterity.id(), score);
+        c
+    }
+
+    #[test]
+    fn initiative_order() {
+        let mut encounter = Encounter::default();
+
+        let mut alert = with_dex(10);
+        alert.add_situational(Situational::new("5E::FEAT::ALERT", |_, ctx| {
+            ctx.tagged(INITIATIVE)
+                .then(|| Box::new(Arithmetic::Add(30)) as _)
+        }));
+
+        let mut slow = with_dex(10);
+        slow.add_situational(Situational::new("HOMEBREW::SLOW", |_, _| {
+            Some(Box::new(Arithmetic::Sub(30)) as _)
+        }));
+
+        let slow = encounter.add(slow);
+        let goblins = encounter.add_group("GOBLIN", (0..3).map(|_| with_dex(14)));
+        let alert = encounter.add(alert);
+
+        encounter.roll_initiative();
+
+        let order = encounter.order();
+        assert_eq!(order[0], alert);
+        assert_eq!(order[4], slow);
+
+        // Grouped monsters act together, on the same roll.
+        assert_eq!(&order[1..4], &goblins[..]);
+        let rolls = goblins
+            .iter()
+            .map(|g| encounter.combatant(*g).initiative())
+            .collect::<Vec<_>>();
+        assert!(rolls.iter().all(|r| *r == rolls[0]));
+    }
+
+    #[test]
+    fn initiative_ties() {
+        let mut encounter = Encounter::default();
+        let nimble = with_dex(18);
+
+        // Only the DEX score differs: modifiers cancel out.
+        let mut clumsy = with_dex(8);
+        clumsy.add_situational(Situational::new("HOMEBREW::EVEN", |_, _| {
+            Some(Box::new(Arithmetic::Add(5)) as _)
+        }));
+
+        let clumsy = encounter.add(clumsy);
+        let nimble = encounter.add(nimble);
+
+        // Can't force the d20s, so just check the tiebreak when they land.
+        for _ in 0..100 {
+            encounter.roll_initiative();
+            let (a, b) = (
+                encounter.combatant(clumsy).initiative(),
+                encounter.combatant(nimble).initiative(),
+            );
+            if a == b {
+                assert_eq!(encounter.order(), &[nimble, clumsy]);
+            }
+        }
+    }
+
+    #[test]
+    fn initiative_advantage() {
+        let mut barbarian = with_dex(10);
+        barbarian.add_edge(Edge::advantage("5E::FEATURE::FERAL_INSTINCT", |_, ctx| {
+            ctx.tagged(INITIATIVE)
+        }));
+
+        let ctx = crate::creature::context::CheckContext::default();
+        assert_eq!(
+            barbarian.check_in(Dexterity, ctx.clone().tag(INITIATIVE))[D20].len(),
+            2
+        );
+        assert_eq!(barbarian.check_in(Dexterity, ctx)[D20].len(), 1);
+    }
+
+    #[test]
+    fn rounds_and_hooks() {
+        let mut encounter = Encounter::default();
+        let a = encounter.add(with_dex(20));
+        let b = encounter.add(with_dex(1));
+
+        let log = Rc::new(RefCell::new(vec![]));
+        let (start, end) = (log.clone(), log.clone());
+        encounter
+            .on_start_of_turn(move |turn, _| start.borrow_mut().push(("start", turn)))
+            .on_end_of_turn(move |turn, _| end.borrow_mut().push(("end", turn)));
+
+        assert_eq!(encounter.next_turn(), None);
+
+        encounter.roll_initiative();
+        let order = encounter.order().to_vec();
+        assert!(order.contains(&a) && order.contains(&b));
+
+        let turns = (0..3)
+            .filter_map(|_| encounter.next_turn())
+            .collect::<Vec<_>>();
+        let turn = |round, i: usize| Turn {
+            round,
+            combatant: order[i],
+        };
+
+        assert_eq!(turns, vec![turn(1, 0), turn(1, 1), turn(2, 0)]);
+        assert_eq!(
+            *log.borrow(),
+            vec![
+                ("start", turn(1, 0)),
+                ("end", turn(1, 0)),
+                ("start", turn(1, 1)),
+                ("end", turn(1, 1)),
+                ("start", turn(2, 0)),
+            ]
+        );
+    }
+
+    #[test]
+    fn action_economy() {
+        let mut encounter = Encounter::default();
+        let mut fast = with_dex(20);
+        fast.set_speed(40);
+
+        let fast = encounter.add(fast);
+        let slow = encounter.add(with_dex(1));
+        encounter.roll_initiative();
+
+        let first = encounter.next_turn().unwrap().combatant;
+        let other = if first == fast { slow } else { fast };
+
+        let creature = encounter.combatant_mut(first).creature_mut();
+        assert_eq!(creature.actions().take(ActionKind::Action), Ok(()));
+        assert_eq!(creature.actions().take(ActionKind::Reaction), Ok(()));
+
+        // Off-turn: reactions only.
+        let waiting = encounter.combatant_mut(other).creature_mut();
+        assert_eq!(
+            waiting.actions().take(ActionKind::Action),
+            Err(ActionError::NotYourTurn(ActionKind::Action))
+        );
+        assert_eq!(waiting.actions().take(ActionKind::Reaction), Ok(()));
+
+        encounter.next_turn();
+        let acting = encounter.combatant_mut(other).creature_mut();
+        let speed = acting.speed();
+        assert!(acting.actions().available(ActionKind::Reaction));
+        assert_eq!(acting.actions().movement(), speed);
+
+        encounter.next_turn();
+        let creature = encounter.combatant_mut(first).creature_mut();
+        assert!(creature.actions().available(ActionKind::Action));
+        assert!(creature.actions().available(ActionKind::Reaction));
+    }
+
+    #[test]
+    fn experience() {
+        let mut encounter = Encounter::default();
+        let party = [
+            encounter.add(Creature::default()),
+            encounter.add(Creature::default()),
+        ];
+        encounter.add_group("GOBLIN", [Creature::default()]);
+
+        assert_eq!(encounter.award(&party, 700), 350);
+        assert_eq!(encounter.award(&party, 301), 150);
+
+        let pc = encounter.combatant(party[1]).creature();
+        assert_eq!(pc.advancement(), Advancement::Experience(500));
+        assert_eq!(pc.pending_levels(), 2);
+        assert_eq!(encounter.award(&[], 100), 0);
+    }
+}
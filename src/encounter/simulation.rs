@@ -0,0 +1,713 @@
+use std::collections::HashMap;
+
+use crate::{
+    ability::Ability,
+    creature::{context::CheckContext, effect::RollKind, Creature},
+    damage::DamageType,
+    dice::{self, D20},
+    grid::{AreaSave, Cell, Layout, Portion, SaveResult},
+    monster::{Attack, Monster},
+    spell::{slots::MAX_SLOT_LEVEL, Effect, OnSave, Spell},
+};
+
+use super::{CombatantId, Encounter};
+
+///
+/// How a simulated creature picks its target, and what it does.
+///
+#[derive(Debug, Clone, Copy, Default, Hash, PartialEq, Eq)]
+pub enum Policy {
+    ///
+    /// Attack the closest enemy still standing.
+    ///
+    #[default]
+    Nearest,
+    ///
+    /// Focus whoever has the fewest hit points left.
+    ///
+    Weakest,
+    ///
+    /// Cast the most damaging spell there's a slot
+    /// for at the closest enemy, then fall back on attacks.
+    ///
+    BestSpell,
+}
+
+///
+/// A creature taking part in a [Simulation].
+///
+#[derive(Debug)]
+pub struct Unit {
+    creature: Creature,
+    side: &'static str,
+    attacks: Vec<Attack>,
+    spells: Vec<(&'static str, &'static dyn Spell)>,
+    policy: Policy,
+    position: Cell,
+    death_saves: bool,
+}
+
+impl Unit {
+    ///
+    /// A character on `side`: it makes death saves at 0 hit points.
+    ///
+    pub fn new(side: &'static str, creature: Creature) -> Self {
+        Self {
+            creature,
+            side,
+            attacks: vec![],
+            spells: vec![],
+            policy: Policy::default(),
+            position: Cell::default(),
+            death_saves: true,
+        }
+    }
+
+    ///
+    /// A monster on `side`, attacking as in its [Monster::round].
+    /// It dies at 0 hit points.
+    ///
+    pub fn monster(side: &'static str, monster: Monster) -> Self {
+        let attacks = monster.round().into_iter().cloned().collect();
+
+        Self {
+            attacks,
+            death_saves: false,
+            ..Self::new(side, monster.into_creature())
+        }
+    }
+
+    ///
+    /// An attack made every turn (several make a Multiattack).
+    ///
+    pub fn attack(mut self, attack: Attack) -> Self {
+        self.attacks.push(attack);
+        self
+    }
+
+    ///
+    /// A damaging spell, cast with `class`'s spellcasting.
+    ///
+    pub fn spell(mut self, class: &'static str, spell: &'static dyn Spell) -> Self {
+        self.spells.push((class, spell));
+        self
+    }
+
+    pub fn policy(mut self, policy: Policy) -> Self {
+        self.policy = policy;
+        self
+    }
+
+    ///
+    /// Position, in 5-foot squares.
+    ///
+    pub fn at(mut self, x: i32, y: i32) -> Self {
+        self.position = Cell::new(x, y);
+        self
+    }
+
+    pub fn resistant(mut self, kind: DamageType) -> Self {
+        self.creature.defenses_mut().resist(kind);
+        self
+    }
+
+    pub fn side(&self) -> &'static str {
+        self.side
+    }
+
+    pub fn creature(&self) -> &Creature {
+        &self.creature
+    }
+}
+
+///
+/// Everything about a [Unit] but its creature,
+/// which takes part in the [Encounter].
+///
+struct Stats {
+    side: &'static str,
+    attacks: Vec<Attack>,
+    spells: Vec<(&'static str, &'static dyn Spell)>,
+    policy: Policy,
+    position: Cell,
+    death_saves: bool,
+}
+
+///
+/// How one simulated fight went.
+///
+#[derive(Debug, Clone, PartialEq)]
+pub struct Outcome {
+    ///
+    /// Last side standing, if any.
+    ///
+    pub winner: Option<&'static str>,
+    pub rounds: usize,
+    ///
+    /// Hit points left, and the maximum, by side.
+    ///
+    pub hp: HashMap<&'static str, (usize, usize)>,
+    ///
+    /// Creatures killed, by side.
+    ///
+    pub deaths: HashMap<&'static str, usize>,
+}
+
+///
+/// How a batch of simulated fights went.
+///
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Report {
+    pub runs: usize,
+    pub wins: HashMap<&'static str, usize>,
+    ///
+    /// Fights still going after the most rounds allowed.
+    ///
+    pub draws: usize,
+    pub average_rounds: f32,
+    ///
+    /// Share of hit points left, by side (from 0 to 1).
+    ///
+    pub average_hp: HashMap<&'static str, f32>,
+    pub average_deaths: HashMap<&'static str, f32>,
+}
+
+impl Report {
+    ///
+    /// Share of fights `side` won (from 0 to 1).
+    ///
+    pub fn win_rate(&self, side: &str) -> f32 {
+        match self.runs {
+            0 => 0.0,
+            runs => self.wins.get(side).copied().unwrap_or(0) as f32 / runs as f32,
+        }
+    }
+}
+
+///
+/// Builds the units for a fresh fight.
+///
+pub type Setup = dyn Fn() -> Vec<Unit>;
+
+///
+/// Headless fights, from initiative until
+/// only one side has anyone left standing.
+///
+/// ***
+/// ```
+/// use xander::{
+///     encounter::{Simulation, Unit},
+///     monster,
+/// };
+///
+/// let simulation = Simulation::new(|| {
+///     vec![
+///         Unit::monster("GOBLINS", monster::goblin()),
+///         Unit::monster("GOBLINS", monster::goblin()).at(1, 0),
+///         Unit::monster("DRAGON", monster::adult_red_dragon()).at(3, 0),
+///     ]
+/// });
+///
+/// let report = simulation.run(100, 42);
+/// assert_eq!(report, simulation.run(100, 42));
+/// assert!(report.win_rate("DRAGON") > 0.9);
+/// ```
+///
+pub struct Simulation {
+    setup: Box<Setup>,
+    max_rounds: usize,
+}
+
+impl Simulation {
+    ///
+    /// Fights stop after this many rounds, as a draw.
+    ///
+    pub const MAX_ROUNDS: usize = 100;
+
+    pub fn new(setup: impl Fn() -> Vec<Unit> + 'static) -> Self {
+        Self {
+            setup: Box::new(setup),
+            max_rounds: Self::MAX_ROUNDS,
+        }
+    }
+
+    pub fn max_rounds(mut self, rounds: usize) -> Self {
+        self.max_rounds = rounds;
+        self
+    }
+
+    ///
+    /// Run `times` fights, with dice seeded by `seed`.
+    ///
+    /// Any seed already set on this thread is put back after.
+    ///
+    pub fn run(&self, times: usize, seed: u64) -> Report {
+        let outcomes = {
+            let _seeded = dice::seeded(seed);
+            (0..times).map(|_| self.fight()).collect::<Vec<_>>()
+        };
+
+        let mut report = Report {
+            runs: times,
+            ..Default::default()
+        };
+
+        for outcome in &outcomes {
+            match outcome.winner {
+                Some(side) => *report.wins.entry(side).or_default() += 1,
+                None => report.draws += 1,
+            }
+            report.average_rounds += outcome.rounds as f32;
+
+            for (side, (hp, max)) in &outcome.hp {
+                *report.average_hp.entry(side).or_default() += *hp as f32 / (*max).max(1) as f32;
+            }
+            for (side, deaths) in &outcome.deaths {
+                *report.average_deaths.entry(side).or_default() += *deaths as f32;
+            }
+        }
+
+        let runs = times.max(1) as f32;
+        report.average_rounds /= runs;
+        report.average_hp.values_mut().for_each(|hp| *hp /= runs);
+        report.average_deaths.values_mut().for_each(|d| *d /= runs);
+        report
+    }
+
+    ///
+    /// Run a single fight.
+    ///
+    pub fn fight(&self) -> Outcome {
+        let mut encounter = Encounter::default();
+        let mut stats = vec![];
+
+        for unit in (self.setup)() {
+            let Unit {
+                creature,
+                side,
+                attacks,
+                spells,
+                policy,
+                position,
+                death_saves,
+            } = unit;
+
+            encounter.add(creature);
+            stats.push(Stats {
+                side,
+                attacks,
+                spells,
+                policy,
+                position,
+                death_saves,
+            });
+        }
+
+        encounter.roll_initiative();
+
+        let mut rounds = 0;
+        while let Some(turn) = encounter.next_turn() {
+            if turn.round > self.max_rounds {
+                break;
+            }
+            rounds = turn.round;
+
+            let me = turn.combatant.0;
+            let creature = &mut encounter.combatants[me].creature;
+            if creature.hp() == 0 {
+                if stats[me].death_saves {
+                    creature.death_save();
+                }
+                continue;
+            }
+
+            take_turn(&mut encounter, &stats, me);
+
+            if standing(&encounter, &stats).len() <= 1 {
+                break;
+            }
+        }
+
+        let mut hp = HashMap::<&'static str, (usize, usize)>::new();
+        let mut deaths = HashMap::<&'static str, usize>::new();
+        for (c, s) in encounter.combatants.iter().zip(&stats) {
+            let (left, max) = hp.entry(s.side).or_default();
+            *left += c.creature.hp();
+            *max += c.creature.max_hp();
+
+            let dead = match s.death_saves {
+                true => c.creature.dead(),
+                false => c.creature.hp() == 0,
+            };
+            *deaths.entry(s.side).or_default() += dead as usize;
+        }
+
+        let standing = standing(&encounter, &stats);
+        Outcome {
+            winner: match standing.as_slice() {
+                [side] => Some(*side),
+                _ => None,
+            },
+            rounds,
+            hp,
+            deaths,
+        }
+    }
+}
+
+impl std::fmt::Debug for Simulation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Simulation")
+            .field("max_rounds", &self.max_rounds)
+            .finish_non_exhaustive()
+    }
+}
+
+///
+/// Sides with anyone above 0 hit points.
+///
+fn standing(encounter: &Encounter, stats: &[Stats]) -> Vec<&'static str> {
+    let mut sides = vec![];
+    for (c, s) in encounter.combatants.iter().zip(stats) {
+        if c.creature.hp() > 0 && !sides.contains(&s.side) {
+            sides.push(s.side);
+        }
+    }
+    sides
+}
+
+fn target(encounter: &Encounter, stats: &[Stats], me: usize) -> Option<usize> {
+    let enemies = encounter
+        .combatants
+        .iter()
+        .zip(stats)
+        .enumerate()
+        .filter(|(_, (c, s))| s.side != stats[me].side && c.creature.hp() > 0);
+
+    match stats[me].policy {
+        Policy::Weakest => enemies.min_by_key(|(_, (c, _))| c.creature.hp()),
+        Policy::Nearest | Policy::BestSpell => enemies
+            .min_by_key(|(_, (_, s))| Layout::default().distance(s.position, stats[me].position)),
+    }
+    .map(|(i, _)| i)
+}
+
+fn take_turn(encounter: &mut Encounter, stats: &[Stats], me: usize) {
+    if stats[me].policy == Policy::BestSpell {
+        if let Some(target) = target(encounter, stats, me) {
+            if cast(encounter, stats, me, target) {
+                return;
+            }
+        }
+    }
+
+    for attack in &stats[me].attacks {
+        match target(encounter, stats, me) {
+            Some(target) => strike(encounter, me, attack, target),
+            None => return,
+        }
+    }
+}
+
+///
+/// Roll to hit `target`'s AC, through the attacker's creature
+/// (effects, hooks, advantage...): a natural 20 always hits,
+/// a natural 1 always misses.
+///
+/// Returns whether it hit, and whether that was a critical hit.
+///
+fn hit(encounter: &Encounter, me: usize, bonus: i32, target: usize) -> (bool, bool) {
+    let creature = &encounter.combatants[me].creature;
+    let (rolls, ctx) = creature.roll_attack(bonus, CheckContext::default());
+    let natural = rolls.kept(&D20).into_iter().max().unwrap_or(0);
+    let ac = encounter.combatants[target].creature.armor_class();
+    let outcome = creature.resolve(RollKind::Attack, &ctx, rolls, ac);
+
+    match natural {
+        20 => (true, true),
+        1 => (false, false),
+        _ => (outcome.success(), false),
+    }
+}
+
+///
+/// Attack, and roll for damage: a critical
+/// hit rolls the damage dice twice.
+///
+/// Attacks calling for a save are saved against
+/// for each type of damage they deal.
+///
+fn strike(encounter: &mut Encounter, me: usize, attack: &Attack, target: usize) {
+    if let Some((ability, dc)) = attack.saving_throw() {
+        for (roll, kind) in attack.damage_rolls() {
+            let damage = roll().total().max(0) as usize;
+            save(
+                encounter,
+                &[CombatantId(target)],
+                ability,
+                dc,
+                OnSave::Half,
+                damage,
+                *kind,
+            );
+        }
+        return;
+    }
+
+    let (hit, critical) = hit(encounter, me, attack.bonus(), target);
+    if !hit {
+        return;
+    }
+
+    for (roll, kind) in attack.damage_rolls() {
+        let mut damage = roll().peek();
+        if critical {
+            damage += roll().dice_total();
+        }
+
+        encounter.combatants[target]
+            .creature
+            .take_damage_of(damage.max(0) as usize, *kind);
+    }
+}
+
+///
+/// Cast the spell expected to deal the most damage at `target`,
+/// using the lowest slot there is for it.
+///
+/// Returns whether anything was cast.
+///
+fn cast(encounter: &mut Encounter, stats: &[Stats], me: usize, target: usize) -> bool {
+    let creature = &mut encounter.combatants[me].creature;
+    let level = creature.level();
+
+    let best = stats[me]
+        .spells
+        .iter()
+        .filter_map(|(class, spell)| {
+            // Not a caster of this class, or nothing to deal.
+            if creature.spell_save_dc(class).is_none() || spell.damage_type().is_none() {
+                return None;
+            }
+
+            let slot = match spell.level() {
+                0 => 0,
+                min => {
+                    (min..=MAX_SLOT_LEVEL).find(|s| creature.spellcasting().available(*s) > 0)?
+                }
+            };
+            Some((*class, *spell, slot, spell.damage(slot, level)?))
+        })
+        .max_by(|a, b| a.3.expected().total_cmp(&b.3.expected()));
+
+    let Some((class, spell, slot, damage)) = best else {
+        return false;
+    };
+    if slot > 0 {
+        let _ = creature.spellcasting().expend(slot);
+    }
+
+    let kind = spell.damage_type().unwrap_or(DamageType::Force);
+    let (bonus, dc) = (
+        creature.spell_attack_bonus(class).unwrap_or(0),
+        creature.spell_save_dc(class).unwrap_or(0),
+    );
+
+    match spell.effect() {
+        Effect::Attack(_) => {
+            let (hit, critical) = hit(encounter, me, bonus, target);
+            if hit {
+                let extra = match critical {
+                    true => spell.damage(slot, level).map_or(0, |r| r.dice_total()),
+                    false => 0,
+                };
+                let damage = (damage.total() + extra).max(0) as usize;
+                encounter.combatants[target]
+                    .creature
+                    .take_damage_of(damage, kind);
+            }
+        }
+        Effect::Save(ability, on_save) => {
+            let damage = damage.total().max(0) as usize;
+            save(
+                encounter,
+                &[CombatantId(target)],
+                ability,
+                dc,
+                on_save,
+                damage,
+                kind,
+            );
+        }
+        Effect::Automatic => {
+            let damage = damage.total().max(0) as usize;
+            encounter.combatants[target]
+                .creature
+                .take_damage_of(damage, kind);
+        }
+    }
+
+    true
+}
+
+///
+/// Have `targets` save against a spell's damage, with
+/// the ability it calls for (see [AreaSave]).
+///
+fn save(
+    encounter: &mut Encounter,
+    targets: &[CombatantId],
+    ability: &'static dyn Ability,
+    dc: i32,
+    on_save: OnSave,
+    damage: usize,
+    kind: DamageType,
+) -> Vec<SaveResult> {
+    let success = match on_save {
+        OnSave::Half => Portion::Half,
+        OnSave::Negates => Portion::Nothing,
+    };
+
+    AreaSave::new(ability, dc)
+        .on_success(success)
+        .resolve(encounter, targets, damage, kind)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        ability::{
+            skills::{Arcana, History},
+            Constitution, Dexterity, Intelligence, Strength,
+        },
+        class::Wizard,
+        creature::Creature,
+        damage::DamageType,
+        dice::{D6, D8},
+        identity::Identity,
+        monster::{self, Attack},
+        spell::{FireBolt, Fireball},
+    };
+
+    use super::{Policy, Simulation, Unit};
+
+    fn fighter() -> Unit {
+        let mut creature = Creature::default();
+        creature
+            .stats()
+            .extend([(Strength.id(), 16), (Constitution.id(), 14)]);
+        creature.set_max_hp(Some(28)).set_armor_class(Some(18));
+
+        Unit::new("PARTY", creature)
+            .attack(Attack::new("Longsword", 5).damage(|| D8(1) + 3, DamageType::Slashing))
+            .attack(Attack::new("Longsword", 5).damage(|| D8(1) + 3, DamageType::Slashing))
+    }
+
+    #[test]
+    fn simulations() {
+        let simulation = Simulation::new(|| {
+            vec![
+                fighter(),
+                Unit::monster("GOBLINS", monster::goblin()).at(1, 0),
+                Unit::monster("GOBLINS", monster::goblin())
+                    .at(5, 5)
+                    .policy(Policy::Weakest),
+            ]
+        });
+
+        let report = simulation.run(200, 7);
+        assert_eq!(report, simulation.run(200, 7));
+        assert_eq!(report.runs, 200);
+        assert_eq!(
+            report.wins.values().sum::<usize>() + report.draws,
+            report.runs
+        );
+        assert!(report.win_rate("PARTY") > 0.8);
+        assert!(report.average_rounds >= 1.0);
+        assert!(report.average_hp["PARTY"] > report.average_hp["GOBLINS"]);
+
+        let outcome = simulation.fight();
+        assert_eq!(outcome.hp["GOBLINS"].1, 14);
+    }
+
+    #[test]
+    fn spells() {
+        let wizard = |levels: usize, hp: usize| {
+            let mut wizard = Creature::default();
+            wizard.stats().insert(Intelligence.id(), 16);
+            wizard
+                .add_level(&Wizard, &[Arcana.id(), History.id()])
+                .unwrap();
+            (1..levels).for_each(|_| {
+                wizard.add_level(&Wizard, &[]).unwrap();
+            });
+            wizard.set_max_hp(Some(hp)).set_armor_class(Some(12));
+
+            Unit::new("WIZARD", wizard)
+                .spell(Wizard.id(), &FireBolt)
+                .spell(Wizard.id(), &Fireball)
+                .policy(Policy::BestSpell)
+        };
+
+        let duel = |levels: usize| {
+            Simulation::new(move || {
+                vec![
+                    wizard(levels, 12),
+                    Unit::monster("GOBLINS", monster::goblin()).at(6, 0),
+                ]
+            })
+        };
+
+        // Only the cantrip to go on, and it can miss.
+        assert_eq!(duel(1).run(50, 3).wins["WIZARD"], 26);
+
+        // Fireball can't miss: Fire Bolt alone only wins 42 of these.
+        let report = duel(5).run(50, 3);
+        assert_eq!(report.wins["WIZARD"], 50);
+        assert_eq!(report.average_rounds, 1.02);
+
+        // Fire does nothing to a red dragon, Fireball included.
+        let simulation = Simulation::new(move || {
+            vec![
+                wizard(5, 500),
+                Unit::monster("DRAGON", monster::adult_red_dragon()).at(6, 0),
+            ]
+        });
+
+        let (hp, max) = simulation.fight().hp["DRAGON"];
+        assert_eq!(hp, max);
+    }
+
+    #[test]
+    fn saving_attacks() {
+        let simulation = Simulation::new(|| {
+            let mut spitter = Creature::default();
+            spitter.set_max_hp(Some(100));
+            vec![
+                Unit::new("SPITTER", spitter).attack(
+                    Attack::save("Spit", &Dexterity, 15).damage(|| D6(4) + 10, DamageType::Acid),
+                ),
+                Unit::monster("GOBLINS", monster::goblin()),
+            ]
+        });
+
+        // Even half of it is enough for a goblin.
+        let report = simulation.run(10, 1);
+        assert_eq!(report.wins["SPITTER"], 10);
+        assert_eq!(report.average_rounds, 1.0);
+    }
+
+    #[test]
+    fn draws() {
+        let simulation = Simulation::new(|| {
+            let mut wall = Creature::default();
+            wall.set_max_hp(Some(100)).set_armor_class(Some(30));
+            vec![Unit::new("A", wall), Unit::monster("B", monster::goblin())]
+        })
+        .max_rounds(3);
+
+        let report = simulation.run(10, 1);
+        assert_eq!(report.draws, 10);
+        assert_eq!(report.average_rounds, 3.0);
+    }
+}
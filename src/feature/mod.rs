@@ -0,0 +1,454 @@
+//!
+//! Feats and class features.
+//!
+//! A [Feature] is applied onto a [Creature] once, and
+//! registers everything it does under its own id, so it
+//! can be cleanly taken away again.
+//!
+//! ### Examples
+//! ***
+//! ```
+//! use xander::{
+//!     ability::{skills::Arcana, Intelligence},
+//!     creature::Creature,
+//!     feature::JackOfAllTrades,
+//!     identity::Identity,
+//! };
+//!
+//! let mut bard = Creature::default();
+//! bard.stats().insert(Intelligence.id(), 10);
+//! bard.add_feature(JackOfAllTrades);
+//!
+//! // Half of the +2 proficiency bonus.
+//! assert!((2..=21).contains(&bard.check(Arcana).total()));
+//! ```
+//!
+
+use std::{fmt::Debug, hash::Hash};
+
+use xander_macros::identify;
+
+use crate::{
+    ability::{Check, Checks, Constitution, Dexterity, Strength, CHECKS},
+    class::Fighter,
+    creature::{
+        context::{RollMode, Situational},
+        effect::RollKind,
+        proficiency::{Fallback, Half, HalfRoundedUp, IntoExpertise, Proficiencies, Source},
+        resource::Resource,
+        rest::Recharge,
+        Creature,
+    },
+    dice::{
+        modifiers::{Advantage, Arithmetic},
+        Roll, D20,
+    },
+    encounter::INITIATIVE,
+    identity::Identity,
+};
+
+///
+/// Anything that changes what a creature can do:
+/// feats, class features, racial traits...
+///
+/// Whatever [Feature::grant] adds (situational modifiers,
+/// edges, hooks, effects) should be registered under [Identity::id],
+/// and proficiencies under [Feature::source], so that
+/// [Creature::remove_feature] can undo it.
+///
+pub trait Feature: Identity {
+    ///
+    /// Source of the proficiencies this grants.
+    ///
+    fn source(&self) -> Source {
+        Source::Feature(self.id())
+    }
+
+    ///
+    /// Ability score increases, by ability id.
+    ///
+    /// Scores are capped at 20.
+    ///
+    fn ability_scores(&self) -> Vec<(&'static str, usize)> {
+        vec![]
+    }
+
+    ///
+    /// Hook into the creature: proficiencies, modifiers,
+    /// effects, roll listeners...
+    ///
+    fn grant(&self, ent: &mut Creature) {
+        let _ = ent;
+    }
+}
+
+///
+/// *Alert* (feat): +5 to initiative.
+///
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
+pub struct Alert;
+
+identify!(Alert, "5E::FEAT::ALERT");
+
+impl Feature for Alert {
+    fn source(&self) -> Source {
+        Source::Feat(self.id())
+    }
+
+    fn grant(&self, ent: &mut Creature) {
+        ent.add_situational(Situational::new(self.id(), |_, ctx| {
+            ctx.tagged(INITIATIVE)
+                .then(|| Box::new(Arithmetic::Add(5)) as _)
+        }));
+    }
+}
+
+///
+/// *Lucky* (feat): three luck points, each letting
+/// the creature roll an extra d20 and keep the best.
+///
+/// Points are a [Resource] under the feat's id, regained on
+/// a long rest, and spent automatically whenever the kept
+/// d20 comes up below `threshold`.
+///
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
+pub struct Lucky {
+    threshold: i32,
+}
+
+identify!(Lucky, "5E::FEAT::LUCKY");
+
+impl Lucky {
+    pub const POINTS: usize = 3;
+
+    pub fn new(threshold: i32) -> Self {
+        Self { threshold }
+    }
+}
+
+impl Default for Lucky {
+    fn default() -> Self {
+        Self::new(10)
+    }
+}
+
+impl Feature for Lucky {
+    fn source(&self) -> Source {
+        Source::Feat(self.id())
+    }
+
+    fn grant(&self, ent: &mut Creature) {
+        let (id, threshold) = (self.id(), self.threshold);
+
+        ent.add_resource(Resource::fixed(id, Self::POINTS, Recharge::LongRest));
+        ent.hooks().after_roll(id, move |ent, _, ctx, rolls| {
+            let d20s = rolls[D20].iter().map(Roll::value);
+            let kept = match ctx.mode() {
+                RollMode::Advantage => d20s.max(),
+                _ => d20s.min(),
+            };
+
+            match (kept, ent.resource(id)) {
+                (Some(kept), Some(points)) if kept < threshold && points.left(ent) > 0 => {
+                    points.spend();
+                    (rolls + D20()).then(Advantage(D20))
+                }
+                _ => rolls,
+            }
+        });
+    }
+}
+
+///
+/// *Second Wind* (fighter 1): once per short rest,
+/// regain 1d10 + fighter level hit points.
+///
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
+pub struct SecondWind;
+
+identify!(SecondWind, "5E::FEATURE::SECOND_WIND");
+
+impl Feature for SecondWind {
+    fn grant(&self, ent: &mut Creature) {
+        ent.add_resource(Resource::fixed(self.id(), 1, Recharge::ShortRest));
+    }
+}
+
+///
+/// *Action Surge* (fighter 2): an extra action once per
+/// short rest, twice from 17th level.
+///
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
+pub struct ActionSurge;
+
+identify!(ActionSurge, "5E::FEATURE::ACTION_SURGE");
+
+impl Feature for ActionSurge {
+    fn grant(&self, ent: &mut Creature) {
+        let uses = |ent: &Creature| match ent.class_level(Fighter.id()) {
+            17.. => 2,
+            _ => 1,
+        };
+        ent.add_resource(Resource::new(self.id(), uses, Recharge::ShortRest));
+    }
+}
+
+///
+/// *Jack of All Trades* (bard 2): half proficiency
+/// on any ability check not already proficient.
+///
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
+pub struct JackOfAllTrades;
+
+identify!(JackOfAllTrades, "5E::FEATURE::JACK_OF_ALL_TRADES");
+
+impl Feature for JackOfAllTrades {
+    fn grant(&self, ent: &mut Creature) {
+        ent.proficiencies()
+            .fallback(CHECKS, Fallback::new(self.source(), Half));
+    }
+}
+
+///
+/// *Remarkable Athlete* (champion 7): half proficiency,
+/// rounded up, on STR, DEX and CON checks not already proficient.
+///
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
+pub struct RemarkableAthlete;
+
+identify!(RemarkableAthlete, "5E::FEATURE::REMARKABLE_ATHLETE");
+
+impl Feature for RemarkableAthlete {
+    fn grant(&self, ent: &mut Creature) {
+        ent.proficiencies().fallback(
+            CHECKS,
+            Fallback::new(self.source(), HalfRoundedUp).only(|ability| {
+                [Strength.id(), Dexterity.id(), Constitution.id()].contains(&ability.id())
+            }),
+        );
+    }
+}
+
+///
+/// *Reliable Talent* (rogue 11): on a proficient
+/// ability check, a d20 roll of 9 or lower counts as 10.
+///
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
+pub struct ReliableTalent;
+
+identify!(ReliableTalent, "5E::FEATURE::RELIABLE_TALENT");
+
+impl Feature for ReliableTalent {
+    fn grant(&self, ent: &mut Creature) {
+        ent.hooks().after_roll(self.id(), |ent, kind, ctx, rolls| {
+            // Explicit proficiency only, Jack of All Trades doesn't count.
+            let proficient = ctx
+                .check()
+                .is_some_and(|check| !ent.explain_id(CHECKS, check).is_empty());
+
+            match (kind, proficient) {
+                (RollKind::Check, true) => {
+                    let d20s = rolls[D20]
+                        .iter()
+                        .map(|r| Roll::from(r.value().max(10)))
+                        .collect::<Vec<_>>();
+                    rolls.replace(&D20, d20s)
+                }
+                _ => rolls,
+            }
+        });
+    }
+}
+
+///
+/// *Evasion* (rogue 7): no damage on a successful
+/// DEX save for half damage, and only half on a failure.
+///
+/// See [crate::grid::AreaSave].
+///
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
+pub struct Evasion;
+
+identify!(Evasion, "5E::FEATURE::EVASION");
+
+impl Feature for Evasion {}
+
+type ExpertiseFn = dyn Fn(&mut Proficiencies, Source);
+
+///
+/// *Expertise* (rogue 1 & 6, bard 3 & 10): double
+/// proficiency bonus on the chosen skills.
+///
+/// Each pick is its own [Source], keyed by its first
+/// skill (no skill can be picked twice).
+///
+/// ***
+/// ```
+/// use xander::{ability::skills::{Stealth, Perception}, feature::Expertise};
+///
+/// let expertise = Expertise::default().with(Stealth).with(Perception);
+/// assert_eq!(expertise.skills(), &["5E::SKILL::STEALTH", "5E::SKILL::PERCEPTION"]);
+/// ```
+///
+#[derive(Default)]
+pub struct Expertise {
+    skills: Vec<&'static str>,
+    grants: Vec<Box<ExpertiseFn>>,
+}
+
+identify!(Expertise, "5E::FEATURE::EXPERTISE");
+
+impl Expertise {
+    pub fn with<C>(mut self, skill: C) -> Self
+    where
+        C: Check + Debug + Hash + Eq + Clone + 'static,
+    {
+        self.skills.push(skill.id());
+        self.grants.push(Box::new(move |profs, source| {
+            profs.grant(source, Checks(skill.clone()).expertise());
+        }));
+        self
+    }
+
+    ///
+    /// Ids of the chosen skills.
+    ///
+    pub fn skills(&self) -> &[&'static str] {
+        &self.skills
+    }
+}
+
+impl Debug for Expertise {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Expertise({:?})", self.skills)
+    }
+}
+
+impl Feature for Expertise {
+    fn source(&self) -> Source {
+        Source::Pick(self.id(), self.skills.first().copied().unwrap_or_default())
+    }
+
+    fn grant(&self, ent: &mut Creature) {
+        let source = self.source();
+        self.grants
+            .iter()
+            .for_each(|grant| grant(ent.proficiencies(), source));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        ability::{
+            skills::{Athletics, History, Stealth},
+            Ability, Checks, Dexterity, Intelligence, Strength, CHECKS,
+        },
+        creature::{context::CheckContext, Creature},
+        dice::D20,
+        encounter::INITIATIVE,
+        identity::Identity,
+    };
+
+    use super::{
+        Alert, Expertise, Feature, JackOfAllTrades, Lucky, ReliableTalent, RemarkableAthlete,
+    };
+
+    fn bonus(ent: &Creature, check: &dyn Identity, ability: &dyn Ability) -> i32 {
+        ent.resolve_id(CHECKS, check.id(), Some(ability))
+            .map(|g| g.prof_type().bonus(ent, &CheckContext::default(), 3))
+            .unwrap_or(0)
+    }
+
+    #[test]
+    fn fallbacks() {
+        let mut ent = Creature::default();
+        ent.add_feature(JackOfAllTrades)
+            .add_feature(RemarkableAthlete);
+
+        // Half of +3: rounded up for athletics, down otherwise.
+        assert_eq!(bonus(&ent, &Athletics, &Strength), 2);
+        assert_eq!(bonus(&ent, &History, &Intelligence), 1);
+
+        ent.remove_feature(JackOfAllTrades.id());
+        assert_eq!(bonus(&ent, &Athletics, &Strength), 2);
+        assert_eq!(bonus(&ent, &History, &Intelligence), 0);
+    }
+
+    #[test]
+    fn expertise() {
+        let mut ent = Creature::default();
+        ent.proficiencies().insert(Checks(Stealth));
+        ent.add_feature(Expertise::default().with(Stealth));
+
+        assert_eq!(bonus(&ent, &Stealth, &Dexterity), 6);
+
+        ent.remove_feature("5E::FEATURE::EXPERTISE");
+        assert_eq!(bonus(&ent, &Stealth, &Dexterity), 3);
+
+        // Taking one pick back leaves the other be.
+        ent.proficiencies().insert(Checks(Athletics));
+        let first = Expertise::default().with(Stealth);
+        let source = first.source();
+        ent.add_feature(first)
+            .add_feature(Expertise::default().with(Athletics));
+
+        ent.proficiencies().remove(source);
+        assert_eq!(bonus(&ent, &Stealth, &Dexterity), 3);
+        assert_eq!(bonus(&ent, &Athletics, &Strength), 6);
+    }
+
+    #[test]
+    fn reliable_talent() {
+        let mut ent = Creature::default();
+        ent.proficiencies().insert(Checks(Stealth));
+        ent.add_feature(ReliableTalent);
+
+        // Proficient: never below 10 + 2.
+        for _ in 0..50 {
+            assert!(ent.check(Stealth).total() >= 12);
+        }
+    }
+
+    #[test]
+    fn alert_and_lucky() {
+        let mut ent = Creature::default();
+        ent.add_feature(Alert);
+
+        let initiative = CheckContext::default().tag(INITIATIVE);
+        assert!((6..=25).contains(&ent.check_in(Dexterity, initiative).total()));
+
+        let lucky = Lucky::new(21);
+        ent.add_feature(lucky);
+
+        // Threshold of 21: every roll spends a point, until none are left.
+        (0..3).for_each(|_| assert_eq!(ent.check(Dexterity)[D20].len(), 2));
+        assert_eq!(ent.check(Dexterity)[D20].len(), 1);
+        assert_eq!(ent.uses(lucky.id()), Some((0, Lucky::POINTS)));
+
+        ent.resource_mut(lucky.id()).unwrap().restore();
+        assert_eq!(ent.check(Dexterity)[D20].len(), 2);
+        assert!(ent.features().any(|f| f.id() == Lucky::default().id()));
+    }
+
+    #[test]
+    fn ability_scores() {
+        #[derive(Debug)]
+        struct Brawny;
+        xander_macros::identify!(Brawny, "HOMEBREW::FEAT::BRAWNY");
+        impl Feature for Brawny {
+            fn ability_scores(&self) -> Vec<(&'static str, usize)> {
+                vec![(Strength.id(), 12)]
+            }
+        }
+
+        let mut ent = Creature::default();
+        ent.stats().insert(Strength.id(), 10);
+
+        ent.add_feature(Brawny);
+        assert_eq!(ent.score::<Strength>(Strength), Some(20));
+
+        ent.remove_feature(Brawny.id());
+        assert_eq!(ent.score::<Strength>(Strength), Some(10));
+    }
+}
@@ -0,0 +1,447 @@
+//!
+//! Creatures on a map: distance, movement, range and areas of effect.
+//!
+//! ### Examples
+//! ***
+//! Backing away from a goblin:
+//! ```
+//! use xander::{
+//!     creature::Creature,
+//!     encounter::Encounter,
+//!     grid::{Cell, Grid},
+//! };
+//!
+//! let mut encounter = Encounter::default();
+//! let fighter = encounter.add(Creature::default());
+//! let goblin = encounter.add(Creature::default());
+//!
+//! let mut grid = Grid::default();
+//! grid.place(fighter, Cell::new(0, 0)).unwrap();
+//! grid.place(goblin, Cell::new(1, 1)).unwrap();
+//! grid.difficult_terrain([Cell::new(-1, 0)]);
+//!
+//! // 5 feet, then 10 through the rubble.
+//! let path = [Cell::new(0, 0), Cell::new(0, -1), Cell::new(-1, 0)];
+//! assert_eq!(grid.path_cost(&path), Ok(15));
+//!
+//! // Leaving the goblin's reach provokes an opportunity attack.
+//! let provoked = grid.move_along(fighter, &path, 30, &[(goblin, 5)]).unwrap();
+//! assert_eq!(provoked, [goblin]);
+//! ```
+//!
+
+mod template;
+
+pub use template::{AreaSave, Portion, SaveResult, Shape, Template};
+
+use std::collections::{HashMap, HashSet};
+
+use crate::{encounter::CombatantId, item::weapon::Weapon};
+
+///
+/// Each square (or hex) is 5 feet across.
+///
+pub const SQUARE: u32 = 5;
+
+///
+/// How diagonal moves are counted on a square grid.
+///
+#[derive(Debug, Clone, Copy, Default, Hash, PartialEq, Eq)]
+pub enum Diagonal {
+    ///
+    /// Every diagonal is 5 feet (PHB default).
+    ///
+    #[default]
+    Five,
+    ///
+    /// Every second diagonal is 10 feet (DMG variant, 5-10-5).
+    ///
+    Alternating,
+}
+
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
+pub enum Layout {
+    Square(Diagonal),
+    ///
+    /// Hexes, in axial coordinates.
+    ///
+    Hex,
+}
+
+impl Default for Layout {
+    fn default() -> Self {
+        Self::Square(Diagonal::default())
+    }
+}
+
+impl Layout {
+    ///
+    /// Squares (or hexes) from `a` to `b`, and how
+    /// many of those are diagonal moves.
+    ///
+    fn steps(&self, a: Cell, b: Cell) -> (u32, u32) {
+        let (dx, dy) = (a.x.abs_diff(b.x), a.y.abs_diff(b.y));
+        match self {
+            Self::Square(_) => (dx.max(dy), dx.min(dy)),
+            Self::Hex => ((dx + dy + (a.x + a.y).abs_diff(b.x + b.y)) / 2, 0),
+        }
+    }
+
+    ///
+    /// Distance from `a` to `b` in feet, ignoring terrain.
+    ///
+    pub fn distance(&self, a: Cell, b: Cell) -> u32 {
+        let (steps, diagonals) = self.steps(a, b);
+        match self {
+            Self::Square(Diagonal::Alternating) => (steps + diagonals / 2) * SQUARE,
+            _ => steps * SQUARE,
+        }
+    }
+
+    pub fn adjacent(&self, a: Cell, b: Cell) -> bool {
+        self.steps(a, b).0 == 1
+    }
+}
+
+///
+/// A square, or (on a [Layout::Hex] grid) a hex.
+///
+#[derive(Debug, Clone, Copy, Default, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Cell {
+    pub x: i32,
+    pub y: i32,
+}
+
+impl Cell {
+    pub const fn new(x: i32, y: i32) -> Self {
+        Self { x, y }
+    }
+}
+
+///
+/// How far an attack can reach.
+///
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
+pub enum Range {
+    ///
+    /// Melee, with this reach in feet.
+    ///
+    Melee(u32),
+    ///
+    /// Ranged, with a normal and long range in feet.
+    ///
+    Ranged(u32, u32),
+}
+
+impl Range {
+    pub fn melee(weapon: &impl Weapon) -> Self {
+        Self::Melee(weapon.reach())
+    }
+
+    ///
+    /// Range of a thrown or ammunition weapon.
+    ///
+    pub fn ranged(weapon: &impl Weapon) -> Option<Self> {
+        weapon
+            .range()
+            .map(|(normal, long)| Self::Ranged(normal, long))
+    }
+}
+
+///
+/// Whether a target is within range.
+///
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
+pub enum Band {
+    Normal,
+    ///
+    /// Beyond normal range: attacks have disadvantage.
+    ///
+    Long,
+}
+
+impl Band {
+    pub fn disadvantage(&self) -> bool {
+        matches!(self, Self::Long)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GridError {
+    ///
+    /// Not on the grid.
+    ///
+    Unplaced(CombatantId),
+    ///
+    /// Someone else is already there.
+    ///
+    Occupied(Cell),
+    ///
+    /// Paths go one square at a time.
+    ///
+    NotAdjacent(Cell, Cell),
+    ///
+    /// Paths start where the creature is.
+    ///
+    WrongStart(Cell),
+    ///
+    /// The path costs more movement than there is.
+    ///
+    TooFar { cost: u32, speed: u32 },
+}
+
+///
+/// Where everyone is, and what the ground is like.
+///
+/// Creatures take up a single cell, whatever their size.
+///
+#[derive(Debug, Clone, Default)]
+pub struct Grid {
+    layout: Layout,
+    difficult: HashSet<Cell>,
+    positions: HashMap<CombatantId, Cell>,
+}
+
+impl Grid {
+    pub fn new(layout: Layout) -> Self {
+        Self {
+            layout,
+            ..Default::default()
+        }
+    }
+
+    pub fn layout(&self) -> Layout {
+        self.layout
+    }
+
+    ///
+    /// Mark cells as difficult terrain: moving
+    /// into them costs twice as much.
+    ///
+    pub fn difficult_terrain(&mut self, cells: impl IntoIterator<Item = Cell>) -> &mut Self {
+        self.difficult.extend(cells);
+        self
+    }
+
+    pub fn is_difficult(&self, cell: Cell) -> bool {
+        self.difficult.contains(&cell)
+    }
+
+    pub fn place(&mut self, id: CombatantId, cell: Cell) -> Result<(), GridError> {
+        match self.occupant(cell) {
+            Some(other) if other != id => Err(GridError::Occupied(cell)),
+            _ => {
+                self.positions.insert(id, cell);
+                Ok(())
+            }
+        }
+    }
+
+    pub fn remove(&mut self, id: CombatantId) -> Option<Cell> {
+        self.positions.remove(&id)
+    }
+
+    pub fn position(&self, id: CombatantId) -> Option<Cell> {
+        self.positions.get(&id).copied()
+    }
+
+    pub fn occupant(&self, cell: Cell) -> Option<CombatantId> {
+        self.positions
+            .iter()
+            .find_map(|(id, c)| (*c == cell).then_some(*id))
+    }
+
+    ///
+    /// Distance between two creatures, in feet.
+    ///
+    pub fn distance(&self, a: CombatantId, b: CombatantId) -> Result<u32, GridError> {
+        let from = self.position(a).ok_or(GridError::Unplaced(a))?;
+        let to = self.position(b).ok_or(GridError::Unplaced(b))?;
+        Ok(self.layout.distance(from, to))
+    }
+
+    ///
+    /// Movement needed to follow `path` (starting cell
+    /// included), counting difficult terrain and diagonals.
+    ///
+    pub fn path_cost(&self, path: &[Cell]) -> Result<u32, GridError> {
+        let mut cost = 0;
+        let mut diagonals = 0;
+
+        for step in path.windows(2) {
+            let (from, to) = (step[0], step[1]);
+            if !self.layout.adjacent(from, to) {
+                return Err(GridError::NotAdjacent(from, to));
+            }
+
+            let (_, diagonal) = self.layout.steps(from, to);
+            diagonals += diagonal;
+
+            let feet = match self.layout {
+                Layout::Square(Diagonal::Alternating) if diagonal == 1 && diagonals % 2 == 0 => {
+                    2 * SQUARE
+                }
+                _ => SQUARE,
+            };
+
+            cost += match self.is_difficult(to) {
+                true => 2 * feet,
+                false => feet,
+            };
+        }
+
+        Ok(cost)
+    }
+
+    ///
+    /// Move `id` along `path`, with `speed` feet of movement.
+    ///
+    /// Returns whichever of `threats` (and their reach,
+    /// in feet) get an opportunity attack, by the creature
+    /// leaving their reach.
+    ///
+    pub fn move_along(
+        &mut self,
+        id: CombatantId,
+        path: &[Cell],
+        speed: u32,
+        threats: &[(CombatantId, u32)],
+    ) -> Result<Vec<CombatantId>, GridError> {
+        let start = self.position(id).ok_or(GridError::Unplaced(id))?;
+        match path.first() {
+            Some(first) if *first != start => return Err(GridError::WrongStart(*first)),
+            _ => {}
+        }
+
+        let cost = self.path_cost(path)?;
+        if cost > speed {
+            return Err(GridError::TooFar { cost, speed });
+        }
+
+        // Moving through others is up to the DM,
+        // but nobody ends up on the same cell.
+        let end = path.last().copied().unwrap_or(start);
+        if self.occupant(end).is_some_and(|other| other != id) {
+            return Err(GridError::Occupied(end));
+        }
+
+        let provoked = threats
+            .iter()
+            .filter(|(threat, _)| *threat != id)
+            .filter_map(|(threat, reach)| {
+                let at = self.position(*threat)?;
+                let within = |cell: &Cell| self.layout.distance(*cell, at) <= *reach;
+                path.windows(2)
+                    .any(|step| within(&step[0]) && !within(&step[1]))
+                    .then_some(*threat)
+            })
+            .collect();
+
+        self.positions.insert(id, end);
+        Ok(provoked)
+    }
+
+    ///
+    /// Can `attacker` hit `target` with an attack of `range`?
+    ///
+    pub fn in_range(
+        &self,
+        attacker: CombatantId,
+        target: CombatantId,
+        range: Range,
+    ) -> Result<Option<Band>, GridError> {
+        let distance = self.distance(attacker, target)?;
+        Ok(match range {
+            Range::Melee(reach) if distance <= reach => Some(Band::Normal),
+            Range::Ranged(normal, _) if distance <= normal => Some(Band::Normal),
+            Range::Ranged(_, long) if distance <= long => Some(Band::Long),
+            _ => None,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        creature::Creature,
+        encounter::Encounter,
+        item::weapon::{Glaive, Longbow, Longsword},
+    };
+
+    use super::{Band, Cell, Diagonal, Grid, GridError, Layout, Range};
+
+    #[test]
+    fn distances() {
+        let (a, b) = (Cell::new(0, 0), Cell::new(3, 2));
+        assert_eq!(Layout::default().distance(a, b), 15);
+        assert_eq!(Layout::Square(Diagonal::Alternating).distance(a, b), 20);
+        assert_eq!(Layout::Hex.distance(a, b), 25);
+        assert_eq!(Layout::Hex.distance(a, Cell::new(2, -2)), 10);
+        assert!(Layout::Hex.adjacent(a, Cell::new(1, -1)));
+        assert!(!Layout::Hex.adjacent(a, Cell::new(1, 1)));
+
+        let grid = Grid::new(Layout::Square(Diagonal::Alternating));
+        let diagonal = [0, 1, 2, 3].map(|i| Cell::new(i, i));
+        assert_eq!(grid.path_cost(&diagonal), Ok(20));
+        assert_eq!(
+            grid.path_cost(&[Cell::new(0, 0), Cell::new(2, 0)]),
+            Err(GridError::NotAdjacent(Cell::new(0, 0), Cell::new(2, 0)))
+        );
+    }
+
+    #[test]
+    fn movement_and_range() {
+        let mut encounter = Encounter::default();
+        let [archer, guard, rogue] = [(); 3].map(|_| encounter.add(Creature::default()));
+
+        let mut grid = Grid::default();
+        grid.place(archer, Cell::new(0, 0)).unwrap();
+        grid.place(guard, Cell::new(2, 0)).unwrap();
+        assert_eq!(
+            grid.place(rogue, Cell::new(2, 0)),
+            Err(GridError::Occupied(Cell::new(2, 0)))
+        );
+        grid.place(rogue, Cell::new(3, 0)).unwrap();
+
+        let bow = Range::ranged(&Longbow).unwrap();
+        assert_eq!(grid.in_range(archer, rogue, bow), Ok(Some(Band::Normal)));
+        assert_eq!(
+            grid.in_range(archer, guard, Range::melee(&Longsword)),
+            Ok(None)
+        );
+        assert_eq!(
+            grid.in_range(archer, guard, Range::melee(&Glaive)),
+            Ok(Some(Band::Normal))
+        );
+
+        grid.place(rogue, Cell::new(100, 0)).unwrap();
+        let band = grid.in_range(archer, rogue, bow).unwrap().unwrap();
+        assert!(band.disadvantage());
+
+        // Moving within the guard's reach doesn't provoke.
+        grid.place(rogue, Cell::new(3, 0)).unwrap();
+        let path = [Cell::new(3, 0), Cell::new(3, 1), Cell::new(2, 1)];
+        assert_eq!(grid.move_along(rogue, &path, 30, &[(guard, 5)]), Ok(vec![]));
+
+        let away = [Cell::new(2, 1), Cell::new(2, 2), Cell::new(2, 3)];
+        assert_eq!(
+            grid.move_along(rogue, &away, 30, &[(guard, 5), (archer, 5)]),
+            Ok(vec![guard])
+        );
+        assert_eq!(grid.position(rogue), Some(Cell::new(2, 3)));
+
+        grid.difficult_terrain([Cell::new(2, 4), Cell::new(2, 5)]);
+        let rubble = [Cell::new(2, 3), Cell::new(2, 4), Cell::new(2, 5)];
+        assert_eq!(
+            grid.move_along(rogue, &rubble, 15, &[]),
+            Err(GridError::TooFar {
+                cost: 20,
+                speed: 15
+            })
+        );
+        assert_eq!(
+            grid.move_along(rogue, &away, 30, &[]),
+            Err(GridError::WrongStart(Cell::new(2, 1)))
+        );
+    }
+}
@@ -0,0 +1,429 @@
+use std::{collections::HashMap, hash::Hash};
+
+use crate::{
+    ability::{Ability, Dexterity},
+    creature::{context::CheckContext, hooks::Outcome},
+    damage::DamageType,
+    encounter::{CombatantId, Encounter},
+    feature::Evasion,
+    identity::Identity,
+};
+
+use super::{Cell, Grid, Layout, SQUARE};
+
+///
+/// Room for rounding, in feet.
+///
+const EPSILON: f32 = 0.01;
+
+///
+/// Shape of an area of effect, with its size in feet.
+///
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
+pub enum Shape {
+    ///
+    /// Radius around the origin (e.g. *Fireball*, 20).
+    ///
+    Sphere(u32),
+    ///
+    /// Side, from the origin as a corner (e.g. *Thunderwave*, 15).
+    ///
+    Cube(u32),
+    ///
+    /// Length: as wide as it is far from the origin
+    /// (e.g. *Burning Hands*, 15).
+    ///
+    Cone(u32),
+    ///
+    /// Length and width (e.g. *Lightning Bolt*, 100 by 5).
+    ///
+    Line(u32, u32),
+}
+
+///
+/// An area of effect placed on the grid.
+///
+/// Cells count as covered when their centre is in the area.
+///
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
+pub struct Template {
+    shape: Shape,
+    origin: Cell,
+    toward: Cell,
+}
+
+impl Template {
+    ///
+    /// `shape` from `origin`, pointing along +x.
+    ///
+    pub fn new(shape: Shape, origin: Cell) -> Self {
+        Self {
+            shape,
+            origin,
+            toward: Cell::new(origin.x + 1, origin.y),
+        }
+    }
+
+    ///
+    /// Point cones, lines and cubes at `cell`.
+    ///
+    pub fn toward(mut self, cell: Cell) -> Self {
+        if cell != self.origin {
+            self.toward = cell;
+        }
+        self
+    }
+
+    pub fn shape(&self) -> Shape {
+        self.shape
+    }
+
+    pub fn origin(&self) -> Cell {
+        self.origin
+    }
+
+    pub fn contains(&self, layout: Layout, cell: Cell) -> bool {
+        let (origin, toward) = (self.origin, self.toward);
+        match self.shape {
+            Shape::Sphere(radius) => layout.distance(origin, cell) <= radius,
+            Shape::Cube(side) => {
+                // Cells away from the corner, heading toward `toward`.
+                let away = |o: i32, t: i32, c: i32| if t < o { o - c } else { c - o };
+                let n = (side / SQUARE) as i32;
+                (0..n).contains(&away(origin.x, toward.x, cell.x))
+                    && (0..n).contains(&away(origin.y, toward.y, cell.y))
+            }
+            Shape::Cone(length) => {
+                let (t, off) = project(layout, origin, toward, cell);
+                t > EPSILON && t <= length as f32 + EPSILON && off <= t / 2.0 + EPSILON
+            }
+            Shape::Line(length, width) => {
+                let (t, off) = project(layout, origin, toward, cell);
+                t > EPSILON && t <= length as f32 + EPSILON && off <= width as f32 / 2.0 + EPSILON
+            }
+        }
+    }
+}
+
+///
+/// Centre of a cell, in feet.
+///
+fn centre(layout: Layout, cell: Cell) -> (f32, f32) {
+    let (x, y) = (cell.x as f32, cell.y as f32);
+    let (x, y) = match layout {
+        Layout::Hex => (x + y / 2.0, y * 3f32.sqrt() / 2.0),
+        Layout::Square(_) => (x, y),
+    };
+    (x * SQUARE as f32, y * SQUARE as f32)
+}
+
+///
+/// How far `cell` is along the direction from `origin`
+/// to `toward`, and how far off to the side.
+///
+fn project(layout: Layout, origin: Cell, toward: Cell, cell: Cell) -> (f32, f32) {
+    let o = centre(layout, origin);
+    let (dx, dy) = {
+        let t = centre(layout, toward);
+        let (dx, dy) = (t.0 - o.0, t.1 - o.1);
+        let len = dx.hypot(dy);
+        (dx / len, dy / len)
+    };
+
+    let c = centre(layout, cell);
+    let (vx, vy) = (c.0 - o.0, c.1 - o.1);
+    (vx * dx + vy * dy, (vx * dy - vy * dx).abs())
+}
+
+impl Grid {
+    ///
+    /// Everyone inside `template`, in no particular order.
+    ///
+    pub fn affected(&self, template: &Template) -> Vec<CombatantId> {
+        self.positions
+            .iter()
+            .filter(|(_, cell)| template.contains(self.layout, **cell))
+            .map(|(id, _)| *id)
+            .collect()
+    }
+}
+
+///
+/// Share of the damage taken.
+///
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
+pub enum Portion {
+    Full,
+    ///
+    /// Rounded down.
+    ///
+    Half,
+    Nothing,
+}
+
+impl Portion {
+    pub fn of(&self, damage: usize) -> usize {
+        match self {
+            Self::Full => damage,
+            Self::Half => damage / 2,
+            Self::Nothing => 0,
+        }
+    }
+}
+
+///
+/// One target's saving throw against an [AreaSave].
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SaveResult {
+    pub target: CombatantId,
+    pub outcome: Outcome,
+    ///
+    /// Damage actually dealt, after resistances.
+    ///
+    pub damage: usize,
+}
+
+///
+/// A saving throw made by everyone in an area, against
+/// damage rolled once for all of them (e.g. *Fireball*:
+/// DEX, half on a success).
+///
+/// [Evasion] applies by itself to DEX saves for half damage,
+/// other exceptions can be set per target with [AreaSave::except].
+///
+/// ***
+/// ```
+/// use xander::{
+///     ability::Dexterity,
+///     creature::Creature,
+///     damage::DamageType,
+///     encounter::Encounter,
+///     grid::{AreaSave, Cell, Grid, Shape, Template},
+/// };
+///
+/// let mut encounter = Encounter::default();
+/// let mut grid = Grid::default();
+/// for x in 0..3 {
+///     let mut ent = Creature::default();
+///     ent.set_max_hp(Some(40));
+///     grid.place(encounter.add(ent), Cell::new(x * 3, 0)).unwrap();
+/// }
+///
+/// let fireball = Template::new(Shape::Sphere(20), Cell::new(0, 1));
+/// let targets = grid.affected(&fireball);
+/// assert_eq!(targets.len(), 2);
+///
+/// let results =
+///     AreaSave::new(&Dexterity, 15).resolve(&mut encounter, &targets, 28, DamageType::Fire);
+/// assert!(results.iter().all(|r| r.damage == 28 || r.damage == 14));
+/// ```
+///
+#[derive(Debug, Clone)]
+pub struct AreaSave {
+    ability: &'static dyn Ability,
+    dc: i32,
+    success: Portion,
+    except: HashMap<CombatantId, (Portion, Portion)>,
+}
+
+impl AreaSave {
+    ///
+    /// Full damage on a failure, half on a success.
+    ///
+    pub fn new(ability: &'static dyn Ability, dc: i32) -> Self {
+        Self {
+            ability,
+            dc,
+            success: Portion::Half,
+            except: HashMap::new(),
+        }
+    }
+
+    ///
+    /// Damage taken on a success instead of half.
+    ///
+    pub fn on_success(mut self, portion: Portion) -> Self {
+        self.success = portion;
+        self
+    }
+
+    ///
+    /// Damage `target` takes on a failure, and on a success.
+    ///
+    pub fn except(mut self, target: CombatantId, failure: Portion, success: Portion) -> Self {
+        self.except.insert(target, (failure, success));
+        self
+    }
+
+    ///
+    /// Roll a save for each of `targets`, dealing them their
+    /// share of `damage` of `kind`, then their resistances.
+    ///
+    pub fn resolve(
+        &self,
+        encounter: &mut Encounter,
+        targets: &[CombatantId],
+        damage: usize,
+        kind: DamageType,
+    ) -> Vec<SaveResult> {
+        targets
+            .iter()
+            .map(|target| {
+                let creature = encounter.combatant_mut(*target).creature_mut();
+                let outcome =
+                    creature.save_against_dyn(self.ability, CheckContext::default(), self.dc);
+
+                let evasion = self.ability.id() == Dexterity.id()
+                    && self.success == Portion::Half
+                    && creature.features().any(|f| f.id() == Evasion.id());
+
+                let (failure, success) = match self.except.get(target) {
+                    Some(portions) => *portions,
+                    None if evasion => (Portion::Half, Portion::Nothing),
+                    None => (Portion::Full, self.success),
+                };
+
+                let damage = match outcome.success() {
+                    true => success.of(damage),
+                    false => failure.of(damage),
+                };
+                let damage = creature.take_damage_of(damage, kind);
+
+                SaveResult {
+                    target: *target,
+                    outcome,
+                    damage,
+                }
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        ability::{Constitution, Dexterity},
+        creature::Creature,
+        damage::DamageType,
+        encounter::Encounter,
+        feature::Evasion,
+        grid::{Cell, Diagonal, Grid, Layout},
+        monster::adult_red_dragon,
+    };
+
+    use super::{AreaSave, Portion, Shape, Template};
+
+    fn count(template: Template, layout: Layout) -> Vec<Cell> {
+        let mut cells = (-25..=25)
+            .flat_map(|x| (-25..=25).map(move |y| Cell::new(x, y)))
+            .filter(|c| template.contains(layout, *c))
+            .collect::<Vec<_>>();
+        cells.sort();
+        cells
+    }
+
+    #[test]
+    fn templates() {
+        let origin = Cell::default();
+        let square = Layout::default();
+
+        let fireball = Template::new(Shape::Sphere(20), origin);
+        assert_eq!(count(fireball, square).len(), 81);
+        assert_eq!(
+            count(fireball, Layout::Square(Diagonal::Alternating)).len(),
+            61
+        );
+        assert_eq!(count(fireball, Layout::Hex).len(), 61);
+
+        let bolt = Template::new(Shape::Line(100, 5), origin);
+        assert_eq!(
+            count(bolt, square),
+            (1..=20).map(|x| Cell::new(x, 0)).collect::<Vec<_>>()
+        );
+
+        let hands = Template::new(Shape::Cone(15), origin).toward(Cell::new(0, -1));
+        let cells = count(hands, square);
+        assert_eq!(cells.len(), 7);
+        assert!(cells.iter().all(|c| c.y < 0));
+
+        let wave = Template::new(Shape::Cube(15), Cell::new(-1, 0)).toward(Cell::new(-2, 1));
+        let cells = count(wave, square);
+        assert_eq!(cells.len(), 9);
+        assert_eq!((cells[0], cells[8]), (Cell::new(-3, 0), Cell::new(-1, 2)));
+    }
+
+    #[test]
+    fn group_saves() {
+        let mut encounter = Encounter::default();
+        let mut grid = Grid::default();
+
+        let mut ids = vec![];
+        for (x, evasion) in [(0, false), (1, true), (9, false)] {
+            let mut ent = Creature::default();
+            ent.set_max_hp(Some(50));
+            if evasion {
+                ent.add_feature(Evasion);
+            }
+            let id = encounter.add(ent);
+            grid.place(id, Cell::new(x, 0)).unwrap();
+            ids.push(id);
+        }
+
+        let mut targets = grid.affected(&Template::new(Shape::Sphere(10), Cell::default()));
+        targets.sort();
+        assert_eq!(targets, ids[..2]);
+
+        let hp = |encounter: &Encounter| {
+            ids.iter()
+                .map(|id| encounter.combatant(*id).creature().hp())
+                .collect::<Vec<_>>()
+        };
+
+        // Nobody makes it...
+        AreaSave::new(&Dexterity, 100).resolve(&mut encounter, &targets, 20, DamageType::Fire);
+        assert_eq!(hp(&encounter), [30, 40, 50]);
+
+        // ...then everyone does.
+        AreaSave::new(&Dexterity, -100).resolve(&mut encounter, &targets, 20, DamageType::Fire);
+        assert_eq!(hp(&encounter), [20, 40, 50]);
+
+        // Evasion is for DEX saves only.
+        AreaSave::new(&Constitution, -100)
+            .on_success(Portion::Nothing)
+            .except(ids[0], Portion::Full, Portion::Full)
+            .resolve(&mut encounter, &targets, 10, DamageType::Cold);
+        assert_eq!(hp(&encounter), [10, 40, 50]);
+
+        let results = AreaSave::new(&Constitution, 100).resolve(
+            &mut encounter,
+            &targets,
+            10,
+            DamageType::Cold,
+        );
+        assert_eq!(results[1].damage, 10);
+        assert!(!results[1].outcome.success());
+
+        // Nor for saves that negate the damage: no halving on a failure.
+        AreaSave::new(&Dexterity, 100)
+            .on_success(Portion::Nothing)
+            .resolve(&mut encounter, &targets[1..], 10, DamageType::Fire);
+        assert_eq!(hp(&encounter), [0, 20, 50]);
+
+        // Resistance applies after the save.
+        let evader = encounter.combatant_mut(ids[1]).creature_mut();
+        evader.defenses_mut().resist(DamageType::Fire);
+        let results = AreaSave::new(&Dexterity, 100).resolve(
+            &mut encounter,
+            &targets[1..],
+            20,
+            DamageType::Fire,
+        );
+        assert_eq!(results[0].damage, 5);
+
+        let dragon = encounter.add(adult_red_dragon().into_creature());
+        let results =
+            AreaSave::new(&Dexterity, 100).resolve(&mut encounter, &[dragon], 28, DamageType::Fire);
+        assert_eq!(results[0].damage, 0);
+    }
+}
@@ -0,0 +1,164 @@
+use core::hash::Hash;
+
+use xander_macros::armor;
+
+use crate::{creature::Creature, identity::Identity};
+
+use super::{self as item, Item};
+
+///
+/// Weight class of a piece of armor.
+///
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
+pub enum ArmorCategory {
+    Light,
+    Medium,
+    Heavy,
+    Shield,
+}
+
+impl Identity for ArmorCategory {
+    fn id(&self) -> &'static str {
+        use ArmorCategory::*;
+
+        match self {
+            Light => "5E::ARMOR_CATEGORY::LIGHT",
+            Medium => "5E::ARMOR_CATEGORY::MEDIUM",
+            Heavy => "5E::ARMOR_CATEGORY::HEAVY",
+            Shield => "5E::ARMOR_CATEGORY::SHIELD",
+        }
+    }
+
+    fn __id() -> &'static str
+    where
+        Self: Sized,
+    {
+        "5E::ARMOR_CATEGORY"
+    }
+}
+
+impl ArmorGroup for ArmorCategory {}
+
+///
+/// Anything a creature can be trained to wear:
+/// either a single piece of armor,
+/// or a whole [ArmorCategory].
+///
+pub trait ArmorGroup: Identity {}
+
+pub trait Armor: Item + ArmorGroup {
+    fn category(&self) -> ArmorCategory;
+
+    ///
+    /// Base armor class, before any DEX modifier.
+    ///
+    /// For shields, this is the bonus granted
+    /// on top of any other armor.
+    ///
+    fn base(&self) -> i32;
+
+    ///
+    /// Strength score needed to
+    /// move at full speed in this armor.
+    ///
+    fn strength(&self) -> Option<usize>;
+
+    ///
+    /// Does wearing this impose disadvantage
+    /// on [crate::ability::skills::Stealth] checks?
+    ///
+    fn stealth_disadvantage(&self) -> bool;
+
+    ///
+    /// Cap on how much of the DEX modifier
+    /// counts towards armor class.
+    ///
+    fn max_dex(&self) -> Option<i32> {
+        use ArmorCategory::*;
+
+        match self.category() {
+            Light => None,
+            Medium => Some(2),
+            Heavy | Shield => Some(0),
+        }
+    }
+
+    ///
+    /// Armor class when worn by someone
+    /// with the given DEX modifier.
+    ///
+    fn armor_class(&self, dex_modifier: i32) -> i32 {
+        match self.max_dex() {
+            // Heavy armor ignores DEX entirely, penalties included.
+            Some(0) => self.base(),
+            Some(max) => self.base() + dex_modifier.min(max),
+            None => self.base() + dex_modifier,
+        }
+    }
+
+    ///
+    /// Trained in this armor,
+    /// or in its whole category?
+    ///
+    fn proficient(&self, ent: &Creature) -> bool
+    where
+        Self: Sized + Clone + Hash + Eq,
+    {
+        ent.proficient(item::Armor(self.clone())).is_some()
+            || ent.proficient(item::Armor(self.category())).is_some()
+    }
+}
+
+armor!(
+    Light(
+        Padded(11, [StealthDisadvantage]),
+        Leather(11),
+        StuddedLeather(12)
+    ),
+    Medium(
+        Hide(12),
+        ChainShirt(13),
+        ScaleMail(14, [StealthDisadvantage]),
+        Breastplate(14),
+        HalfPlate(15, [StealthDisadvantage])
+    ),
+    Heavy(
+        RingMail(14, [StealthDisadvantage]),
+        ChainMail(16, [Strength(13), StealthDisadvantage]),
+        Splint(17, [Strength(15), StealthDisadvantage]),
+        Plate(18, [Strength(15), StealthDisadvantage])
+    ),
+    Shield(
+        ///
+        /// A wooden or metal shield, carried in one hand.
+        ///
+        Shield(2)
+    )
+);
+
+#[cfg(test)]
+mod tests {
+    use crate::item::armor::{Armor, ArmorCategory};
+
+    use super::{Breastplate, Leather, Padded, Plate, Shield};
+
+    #[test]
+    fn armor_class() {
+        assert_eq!(Leather.armor_class(4), 15);
+        assert_eq!(Breastplate.armor_class(4), 16);
+        assert_eq!(Breastplate.armor_class(-1), 13);
+        assert_eq!(Plate.armor_class(4), 18);
+        assert_eq!(Plate.armor_class(-1), 18);
+        assert_eq!(Shield.category(), ArmorCategory::Shield);
+    }
+
+    #[test]
+    fn requirements() {
+        assert_eq!(Plate.strength(), Some(15));
+        assert!(Plate.stealth_disadvantage());
+
+        assert_eq!(Padded.strength(), None);
+        assert!(Padded.stealth_disadvantage());
+        assert!(!Leather.stealth_disadvantage());
+    }
+}
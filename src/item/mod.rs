@@ -0,0 +1,50 @@
+//!
+//! Equipment: weapons, armor and the like.
+//!
+//! ### Examples
+//! ***
+//! Rolling damage with a preset weapon:
+//! ```
+//! use xander::item::weapon::*;
+//!
+//! fn main() {
+//!     let damage = Longsword.damage();
+//!     println!("The longsword deals {} {:?} damage!", damage.total(), Longsword.damage_type())
+//! }
+//! ```
+//!
+
+pub mod armor;
+pub mod tool;
+pub mod weapon;
+
+use xander_macros::Proficiency;
+
+use crate::{creature::proficiency::Proficiency, identity::Identity};
+
+use self::{armor::ArmorGroup, tool::Tool, weapon::WeaponGroup};
+
+///
+/// Supertrait for anything that can be
+/// carried, worn or wielded.
+///
+pub trait Item: Identity {}
+
+///
+/// Proficiency with tools.
+///
+#[Proficiency("5E::PROFICIENCY::TOOLS")]
+pub struct Tools(Tool);
+
+///
+/// Training with armor, by piece or category.
+///
+#[Proficiency("5E::PROFICIENCY::ARMOR")]
+pub struct Armor(ArmorGroup);
+
+///
+/// Proficiency with weapons, either
+/// individually or by category (e.g. martial).
+///
+#[Proficiency("5E::PROFICIENCY::WEAPONS")]
+pub struct Weapons(WeaponGroup);
@@ -0,0 +1,73 @@
+use core::hash::Hash;
+
+use xander_macros::tools;
+
+use crate::{creature::Creature, identity::Identity};
+
+use super::{Item, Tools};
+
+///
+/// Broad groups of tools.
+///
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
+pub enum ToolCategory {
+    Artisan,
+    Gaming,
+    Musical,
+    Kit,
+    Vehicle,
+}
+
+///
+/// Tools don't have a fixed ability:
+/// the check being made decides that.
+///
+/// ***
+/// **Example**: [ThievesTools] with [crate::ability::skills::SleightOfHand]
+///
+pub trait Tool: Item {
+    fn category(&self) -> ToolCategory;
+
+    fn proficient(&self, ent: &Creature) -> bool
+    where
+        Self: Sized + Clone + Hash + Eq,
+    {
+        ent.proficient(Tools(self.clone())).is_some()
+    }
+}
+
+tools!(
+    Artisan(
+        AlchemistsSupplies,
+        BrewersSupplies,
+        CalligraphersSupplies,
+        CarpentersTools,
+        CartographersTools,
+        CobblersTools,
+        CooksUtensils,
+        GlassblowersTools,
+        JewelersTools,
+        LeatherworkersTools,
+        MasonsTools,
+        PaintersSupplies,
+        PottersTools,
+        SmithsTools,
+        TinkersTools,
+        WeaversTools,
+        WoodcarversTools
+    ),
+    Gaming(DiceSet, DragonchessSet, PlayingCardSet, ThreeDragonAnteSet),
+    Musical(Bagpipes, Drum, Dulcimer, Flute, Horn, Lute, Lyre, PanFlute, Shawm, Viol),
+    Kit(
+        DisguiseKit,
+        ForgeryKit,
+        HerbalismKit,
+        NavigatorsTools,
+        PoisonersKit,
+        ///
+        /// Picks locks and disarms traps.
+        ///
+        ThievesTools
+    ),
+    Vehicle(LandVehicles, WaterVehicles)
+);
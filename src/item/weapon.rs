@@ -0,0 +1,259 @@
+use core::hash::Hash;
+
+use xander_macros::weapons;
+
+use crate::{
+    creature::Creature,
+    damage::DamageType,
+    dice::{Rolls, D, D10, D12, D4, D6, D8},
+    identity::Identity,
+};
+
+use super::{Item, Weapons};
+
+///
+/// Training required to wield a weapon.
+///
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
+pub enum WeaponCategory {
+    Simple,
+    Martial,
+}
+
+impl Identity for WeaponCategory {
+    fn id(&self) -> &'static str {
+        match self {
+            WeaponCategory::Simple => "5E::WEAPON_CATEGORY::SIMPLE",
+            WeaponCategory::Martial => "5E::WEAPON_CATEGORY::MARTIAL",
+        }
+    }
+
+    fn __id() -> &'static str
+    where
+        Self: Sized,
+    {
+        "5E::WEAPON_CATEGORY"
+    }
+}
+
+impl WeaponGroup for WeaponCategory {}
+
+///
+/// How a weapon is used to attack.
+///
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
+pub enum WeaponKind {
+    Melee,
+    Ranged,
+}
+
+///
+/// Damage expression, rolled on use.
+///
+pub type DamageRoll = fn() -> Rolls;
+
+///
+/// Special rules attached to a weapon.
+///
+/// Ranges are given in feet, as `(normal, long)`.
+///
+#[derive(Debug, Clone, Copy)]
+pub enum Property {
+    Ammunition(u32, u32),
+    Finesse,
+    Heavy,
+    Light,
+    Loading,
+    Reach,
+    Special,
+    Thrown(u32, u32),
+    TwoHanded,
+    ///
+    /// Damage when wielded with two hands.
+    ///
+    Versatile(DamageRoll),
+}
+
+///
+/// Anything a creature can be proficient with
+/// as a weapon: either a single weapon,
+/// or a whole [WeaponCategory].
+///
+pub trait WeaponGroup: Identity {}
+
+pub trait Weapon: Item + WeaponGroup {
+    fn category(&self) -> WeaponCategory;
+
+    fn kind(&self) -> WeaponKind;
+
+    ///
+    /// Roll this weapon's damage dice.
+    ///
+    fn damage(&self) -> Rolls;
+
+    fn damage_type(&self) -> DamageType;
+
+    fn properties(&self) -> Vec<Property>;
+
+    fn finesse(&self) -> bool {
+        self.properties()
+            .iter()
+            .any(|p| matches!(p, Property::Finesse))
+    }
+
+    fn heavy(&self) -> bool {
+        self.properties()
+            .iter()
+            .any(|p| matches!(p, Property::Heavy))
+    }
+
+    fn light(&self) -> bool {
+        self.properties()
+            .iter()
+            .any(|p| matches!(p, Property::Light))
+    }
+
+    fn two_handed(&self) -> bool {
+        self.properties()
+            .iter()
+            .any(|p| matches!(p, Property::TwoHanded))
+    }
+
+    ///
+    /// Roll the two-handed damage dice,
+    /// if this weapon is versatile.
+    ///
+    fn versatile(&self) -> Option<Rolls> {
+        self.properties().iter().find_map(|p| match p {
+            Property::Versatile(damage) => Some(damage()),
+            _ => None,
+        })
+    }
+
+    ///
+    /// How far (in feet) this weapon can hit in melee.
+    ///
+    fn reach(&self) -> u32 {
+        match self
+            .properties()
+            .iter()
+            .any(|p| matches!(p, Property::Reach))
+        {
+            true => 10,
+            false => 5,
+        }
+    }
+
+    ///
+    /// `(normal, long)` range in feet, for thrown
+    /// and ammunition weapons.
+    ///
+    fn range(&self) -> Option<(u32, u32)> {
+        self.properties().iter().find_map(|p| match p {
+            Property::Ammunition(normal, long) | Property::Thrown(normal, long) => {
+                Some((*normal, *long))
+            }
+            _ => None,
+        })
+    }
+
+    ///
+    /// Proficient with this weapon,
+    /// or with its whole category?
+    ///
+    fn proficient(&self, ent: &Creature) -> bool
+    where
+        Self: Sized + Clone + Hash + Eq,
+    {
+        ent.proficient(Weapons(self.clone())).is_some()
+            || ent.proficient(Weapons(self.category())).is_some()
+    }
+}
+
+weapons!(
+    Simple Melee(
+        Club(D4(1), Bludgeoning, [Light]),
+        Dagger(D4(1), Piercing, [Finesse, Light, Thrown(20, 60)]),
+        Greatclub(D8(1), Bludgeoning, [TwoHanded]),
+        Handaxe(D6(1), Slashing, [Light, Thrown(20, 60)]),
+        Javelin(D6(1), Piercing, [Thrown(30, 120)]),
+        LightHammer(D4(1), Bludgeoning, [Light, Thrown(20, 60)]),
+        Mace(D6(1), Bludgeoning),
+        Quarterstaff(D6(1), Bludgeoning, [Versatile(D8(1))]),
+        Sickle(D4(1), Slashing, [Light]),
+        Spear(D6(1), Piercing, [Thrown(20, 60), Versatile(D8(1))])
+    ),
+    Simple Ranged(
+        LightCrossbow(D8(1), Piercing, [Ammunition(80, 320), Loading, TwoHanded]),
+        Dart(D4(1), Piercing, [Finesse, Thrown(20, 60)]),
+        Shortbow(D6(1), Piercing, [Ammunition(80, 320), TwoHanded]),
+        Sling(D4(1), Bludgeoning, [Ammunition(30, 120)])
+    ),
+    Martial Melee(
+        Battleaxe(D8(1), Slashing, [Versatile(D10(1))]),
+        Flail(D8(1), Bludgeoning),
+        Glaive(D10(1), Slashing, [Heavy, Reach, TwoHanded]),
+        Greataxe(D12(1), Slashing, [Heavy, TwoHanded]),
+        Greatsword(D6(2), Slashing, [Heavy, TwoHanded]),
+        Halberd(D10(1), Slashing, [Heavy, Reach, TwoHanded]),
+        ///
+        /// Disadvantage when attacking within 5 feet,
+        /// and needs two hands when unmounted.
+        ///
+        Lance(D12(1), Piercing, [Reach, Special]),
+        Longsword(D8(1), Slashing, [Versatile(D10(1))]),
+        Maul(D6(2), Bludgeoning, [Heavy, TwoHanded]),
+        Morningstar(D8(1), Piercing),
+        Pike(D10(1), Piercing, [Heavy, Reach, TwoHanded]),
+        Rapier(D8(1), Piercing, [Finesse]),
+        Scimitar(D6(1), Slashing, [Finesse, Light]),
+        Shortsword(D6(1), Piercing, [Finesse, Light]),
+        Trident(D6(1), Piercing, [Thrown(20, 60), Versatile(D8(1))]),
+        WarPick(D8(1), Piercing),
+        Warhammer(D8(1), Bludgeoning, [Versatile(D10(1))]),
+        Whip(D4(1), Slashing, [Finesse, Reach])
+    ),
+    Martial Ranged(
+        Blowgun(D(1)(1), Piercing, [Ammunition(25, 100), Loading]),
+        HandCrossbow(D6(1), Piercing, [Ammunition(30, 120), Light, Loading]),
+        HeavyCrossbow(D10(1), Piercing, [Ammunition(100, 400), Heavy, Loading, TwoHanded]),
+        Longbow(D8(1), Piercing, [Ammunition(150, 600), Heavy, TwoHanded])
+    )
+);
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        damage::DamageType,
+        dice::{D10, D6},
+        identity::Identity,
+        item::weapon::{Weapon, WeaponCategory, WeaponKind},
+    };
+
+    use super::{Dagger, Greatsword, Longbow, Longsword, Mace, Whip};
+
+    #[test]
+    fn damage() {
+        let damage = Greatsword.damage();
+
+        assert_eq!(damage[D6].len(), 2);
+        assert_eq!(Greatsword.damage_type(), DamageType::Slashing);
+        assert_eq!(Greatsword.id(), "5E::WEAPON::GREATSWORD");
+    }
+
+    #[test]
+    fn properties() {
+        assert!(Dagger.finesse() && Dagger.light());
+        assert_eq!(Dagger.range(), Some((20, 60)));
+        assert_eq!(Dagger.kind(), WeaponKind::Melee);
+
+        assert_eq!(Longbow.range(), Some((150, 600)));
+        assert!(Longbow.heavy() && Longbow.two_handed());
+        assert_eq!(Longbow.category(), WeaponCategory::Martial);
+
+        assert_eq!(Whip.reach(), 10);
+        assert_eq!(Mace.reach(), 5);
+        assert!(Mace.versatile().is_none());
+        assert_eq!(Longsword.versatile().map(|r| r[D10].len()), Some(1));
+    }
+}
@@ -0,0 +1,41 @@
+//!
+//! Languages, and proficiency in speaking them.
+//!
+
+use xander_macros::{languages, Proficiency};
+
+use crate::{creature::proficiency::Proficiency, identity::Identity};
+
+///
+/// How widespread a language is.
+///
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
+pub enum LanguageKind {
+    Standard,
+    Exotic,
+}
+
+pub trait Language: Identity {
+    fn kind(&self) -> LanguageKind;
+}
+
+///
+/// Languages a creature can speak,
+/// read and write.
+///
+#[Proficiency("5E::PROFICIENCY::LANGUAGES")]
+pub struct Languages(Language);
+
+languages!(
+    Standard(Common, Dwarvish, Elvish, Giant, Gnomish, Goblin, Halfling, Orc),
+    Exotic(
+        Abyssal,
+        Celestial,
+        DeepSpeech,
+        Draconic,
+        Infernal,
+        Primordial,
+        Sylvan,
+        Undercommon
+    )
+);
@@ -11,8 +11,18 @@
 )]
 
 pub mod ability;
+pub mod class;
 pub mod creature;
+pub mod damage;
 pub mod dice;
+pub mod encounter;
+pub mod feature;
+pub mod grid;
 pub mod identity;
+pub mod item;
+pub mod language;
+pub mod monster;
+pub mod origin;
+pub mod spell;
 
 pub use identity::Identity;
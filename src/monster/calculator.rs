@@ -0,0 +1,319 @@
+use super::{Attack, Cr, Monster};
+
+///
+/// One row of the DMG's *Monster Statistics by Challenge Rating*.
+///
+struct Row {
+    cr: Cr,
+    ac: i32,
+    ///
+    /// Most hit points at this CR.
+    ///
+    hp: usize,
+    attack_bonus: i32,
+    ///
+    /// Most damage per round at this CR.
+    ///
+    damage: usize,
+    save_dc: i32,
+}
+
+const fn row(cr: Cr, ac: i32, hp: usize, attack_bonus: i32, damage: usize, save_dc: i32) -> Row {
+    Row {
+        cr,
+        ac,
+        hp,
+        attack_bonus,
+        damage,
+        save_dc,
+    }
+}
+
+const TABLE: [Row; 34] = [
+    row(Cr::Zero, 13, 6, 3, 1, 13),
+    row(Cr::Eighth, 13, 35, 3, 3, 13),
+    row(Cr::Quarter, 13, 49, 3, 5, 13),
+    row(Cr::Half, 13, 70, 3, 8, 13),
+    row(Cr::Whole(1), 13, 85, 3, 14, 13),
+    row(Cr::Whole(2), 13, 100, 3, 20, 13),
+    row(Cr::Whole(3), 13, 115, 4, 26, 13),
+    row(Cr::Whole(4), 14, 130, 5, 32, 14),
+    row(Cr::Whole(5), 15, 145, 6, 38, 15),
+    row(Cr::Whole(6), 15, 160, 6, 44, 15),
+    row(Cr::Whole(7), 15, 175, 6, 50, 15),
+    row(Cr::Whole(8), 16, 190, 7, 56, 16),
+    row(Cr::Whole(9), 16, 205, 7, 62, 16),
+    row(Cr::Whole(10), 17, 220, 7, 68, 16),
+    row(Cr::Whole(11), 17, 235, 8, 74, 17),
+    row(Cr::Whole(12), 17, 250, 8, 80, 17),
+    row(Cr::Whole(13), 18, 265, 8, 86, 18),
+    row(Cr::Whole(14), 18, 280, 8, 92, 18),
+    row(Cr::Whole(15), 18, 295, 8, 98, 18),
+    row(Cr::Whole(16), 18, 310, 9, 104, 18),
+    row(Cr::Whole(17), 19, 325, 10, 110, 19),
+    row(Cr::Whole(18), 19, 340, 10, 116, 19),
+    row(Cr::Whole(19), 19, 355, 10, 122, 19),
+    row(Cr::Whole(20), 19, 400, 10, 140, 19),
+    row(Cr::Whole(21), 19, 445, 11, 158, 20),
+    row(Cr::Whole(22), 19, 490, 11, 176, 20),
+    row(Cr::Whole(23), 19, 535, 11, 194, 20),
+    row(Cr::Whole(24), 19, 580, 12, 212, 21),
+    row(Cr::Whole(25), 19, 625, 12, 230, 21),
+    row(Cr::Whole(26), 19, 670, 12, 248, 21),
+    row(Cr::Whole(27), 19, 715, 13, 266, 22),
+    row(Cr::Whole(28), 19, 760, 13, 284, 22),
+    row(Cr::Whole(29), 19, 805, 13, 302, 22),
+    row(Cr::Whole(30), 19, 850, 14, 320, 23),
+];
+
+fn row_for(cr: Cr) -> &'static Row {
+    &TABLE[cr.index()]
+}
+
+///
+/// First row able to hold `value`, or the last one.
+///
+fn find(value: f32, max: impl Fn(&Row) -> usize) -> &'static Row {
+    TABLE
+        .iter()
+        .find(|row| value <= max(row) as f32)
+        .unwrap_or(&TABLE[TABLE.len() - 1])
+}
+
+///
+/// Damage types resisted (or ignored) before they make for effective
+/// hit points: as many as nonmagical weapons deal (B/P/S).
+///
+const SEVERAL: usize = 3;
+
+///
+/// What a monster's CR is worked out from.
+///
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Profile {
+    pub hp: usize,
+    pub ac: i32,
+    ///
+    /// Damage types resisted (and not ignored outright).
+    ///
+    pub resistances: usize,
+    ///
+    /// Damage types ignored outright.
+    ///
+    pub immunities: usize,
+    ///
+    /// Average damage per round.
+    ///
+    pub damage: f32,
+    pub attack_bonus: i32,
+    ///
+    /// For monsters relying on saving throws over attack rolls.
+    ///
+    pub save_dc: Option<i32>,
+}
+
+impl Profile {
+    ///
+    /// Damage per round of `monster` is that of its
+    /// [Monster::round]; anything else it does each round
+    /// (e.g. a breath weapon) can be added on after.
+    ///
+    /// Offense goes by save DC when most of that damage
+    /// calls for a saving throw.
+    ///
+    pub fn of(monster: &Monster) -> Self {
+        let (saves, rolled) = monster
+            .round()
+            .into_iter()
+            .partition::<Vec<_>, _>(|a| a.saving_throw().is_some());
+        let damage = |attacks: &[&Attack]| attacks.iter().map(|a| a.expected_damage()).sum();
+        let (save_damage, rolled_damage): (f32, f32) = (damage(&saves), damage(&rolled));
+        let save_dc = saves
+            .iter()
+            .filter_map(|a| a.saving_throw())
+            .map(|(_, dc)| dc)
+            .max();
+
+        let defenses = monster.creature().defenses();
+        let immunities = defenses.immunities();
+
+        Self {
+            hp: monster.creature().max_hp(),
+            ac: monster.creature().armor_class(),
+            resistances: defenses
+                .resistances()
+                .iter()
+                .filter(|t| !immunities.contains(t))
+                .count(),
+            immunities: immunities.len(),
+            damage: save_damage + rolled_damage,
+            attack_bonus: rolled.iter().map(|a| a.bonus()).max().unwrap_or(0),
+            save_dc: save_dc.filter(|_| save_damage > rolled_damage),
+        }
+    }
+
+    ///
+    /// Hit points, scaled for resistances and immunities
+    /// (to [SEVERAL] damage types or more) by the CR
+    /// those hit points would otherwise have.
+    ///
+    pub fn effective_hp(&self) -> f32 {
+        let expected = find(self.hp as f32, |row| row.hp).cr;
+        let (resistant, immune) = match expected.value() {
+            v if v <= 4.0 => (2.0, 2.0),
+            v if v <= 10.0 => (1.5, 2.0),
+            v if v <= 16.0 => (1.25, 1.5),
+            _ => (1.0, 1.25),
+        };
+
+        let multiplier = if self.immunities >= SEVERAL {
+            immune
+        } else if self.resistances + self.immunities >= SEVERAL {
+            resistant
+        } else {
+            1.0
+        };
+
+        self.hp as f32 * multiplier
+    }
+
+    ///
+    /// Work out the challenge rating.
+    ///
+    pub fn estimate(&self) -> Breakdown {
+        let effective_hp = self.effective_hp();
+        let hp = find(effective_hp, |row| row.hp);
+        // Every 2 points off the expected AC moves CR by one.
+        let defensive = hp.cr.step((self.ac - hp.ac) / 2);
+
+        let damage = find(self.damage, |row| row.damage);
+        let offensive = match self.save_dc {
+            Some(dc) => damage.cr.step((dc - damage.save_dc) / 2),
+            None => damage
+                .cr
+                .step((self.attack_bonus - damage.attack_bonus) / 2),
+        };
+
+        Breakdown {
+            effective_hp,
+            hp: hp.cr,
+            defensive,
+            damage: self.damage,
+            damage_cr: damage.cr,
+            offensive,
+            cr: Cr::nearest((defensive.value() + offensive.value()) / 2.0),
+        }
+    }
+}
+
+///
+/// How a challenge rating was worked out.
+///
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Breakdown {
+    pub effective_hp: f32,
+    ///
+    /// CR from effective hit points alone.
+    ///
+    pub hp: Cr,
+    ///
+    /// CR from hit points, adjusted for AC.
+    ///
+    pub defensive: Cr,
+    pub damage: f32,
+    ///
+    /// CR from damage per round alone.
+    ///
+    pub damage_cr: Cr,
+    ///
+    /// CR from damage, adjusted for attack bonus or save DC.
+    ///
+    pub offensive: Cr,
+    ///
+    /// Average of defensive and offensive CR.
+    ///
+    pub cr: Cr,
+}
+
+///
+/// Expected AC, attack bonus and save DC at `cr`.
+///
+pub fn expected(cr: Cr) -> (i32, i32, i32) {
+    let row = row_for(cr);
+    (row.ac, row.attack_bonus, row.save_dc)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        ability::Dexterity,
+        damage::DamageType,
+        dice::D6,
+        monster::{self, Attack, Cr, Monster},
+    };
+
+    use super::{expected, Profile};
+
+    #[test]
+    fn estimates() {
+        assert_eq!(expected(Cr::Whole(17)), (19, 10, 19));
+
+        let ogre = Profile {
+            hp: 59,
+            ac: 11,
+            damage: 13.0,
+            attack_bonus: 6,
+            ..Default::default()
+        }
+        .estimate();
+
+        // 59 HP is CR 1/2, down one for AC 11; 13 damage
+        // is CR 1, up one for +6 to hit.
+        assert_eq!((ogre.hp, ogre.defensive), (Cr::Half, Cr::Quarter));
+        assert_eq!(
+            (ogre.damage_cr, ogre.offensive),
+            (Cr::Whole(1), Cr::Whole(2))
+        );
+        assert_eq!(ogre.cr, Cr::Whole(1));
+
+        let ghost = Profile {
+            hp: 45,
+            ac: 11,
+            resistances: 7,
+            immunities: 3,
+            damage: 17.0,
+            save_dc: Some(13),
+            ..Default::default()
+        };
+        assert_eq!(ghost.effective_hp(), 90.0);
+        assert_eq!(ghost.estimate().hp, Cr::Whole(2));
+
+        let dragon = monster::adult_red_dragon();
+        let mut profile = Profile::of(&dragon);
+        // Bite (19 + 7) and two claws (15 each).
+        assert_eq!(profile.damage, 56.0);
+        assert_eq!(profile.attack_bonus, 14);
+
+        // Fire Breath (63) every other round or so.
+        profile.damage += 63.0 / 2.0;
+        let breakdown = profile.estimate();
+        assert_eq!(breakdown.defensive, Cr::Whole(13));
+        assert_eq!(breakdown.offensive, Cr::Whole(17));
+        assert_eq!(breakdown.cr, Cr::Whole(15));
+
+        // Fire alone is not enough for effective hit points.
+        assert_eq!(Profile::of(&dragon).immunities, 1);
+        assert_eq!(profile.effective_hp(), 256.0);
+
+        let tough = monster::goblin()
+            .resistant(DamageType::Bludgeoning)
+            .resistant(DamageType::Piercing)
+            .resistant(DamageType::Slashing);
+        assert_eq!(Profile::of(&tough).effective_hp(), 14.0);
+
+        let spitter = Monster::new("Spitter", Cr::Whole(1))
+            .attack(Attack::save("Spit", &Dexterity, 13).damage(|| D6(4), DamageType::Acid));
+        let profile = Profile::of(&spitter);
+        assert_eq!((profile.damage, profile.save_dc), (14.0, Some(13)));
+    }
+}
@@ -0,0 +1,146 @@
+///
+/// Challenge rating: 0, 1/8, 1/4, 1/2, then 1 to 30.
+///
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Cr {
+    Zero,
+    Eighth,
+    Quarter,
+    Half,
+    Whole(u8),
+}
+
+///
+/// Experience points by CR, from 1 to 30.
+///
+const XP: [usize; 30] = [
+    200, 450, 700, 1_100, 1_800, 2_300, 2_900, 3_900, 5_000, 5_900, 7_200, 8_400, 10_000, 11_500,
+    13_000, 15_000, 18_000, 20_000, 22_000, 25_000, 33_000, 41_000, 50_000, 62_000, 75_000, 90_000,
+    105_000, 120_000, 135_000, 155_000,
+];
+
+impl Cr {
+    ///
+    /// Highest challenge rating.
+    ///
+    pub const MAX: Cr = Cr::Whole(30);
+
+    ///
+    /// Every challenge rating, lowest first.
+    ///
+    pub fn all() -> impl Iterator<Item = Cr> {
+        [Cr::Zero, Cr::Eighth, Cr::Quarter, Cr::Half]
+            .into_iter()
+            .chain((1..=30).map(Cr::Whole))
+    }
+
+    ///
+    /// Position in [Cr::all].
+    ///
+    pub(crate) fn index(&self) -> usize {
+        match self {
+            Cr::Zero => 0,
+            Cr::Eighth => 1,
+            Cr::Quarter => 2,
+            Cr::Half => 3,
+            Cr::Whole(n) => 3 + (*n).clamp(1, 30) as usize,
+        }
+    }
+
+    ///
+    /// Move up (or down) some challenge ratings,
+    /// staying between 0 and [Cr::MAX].
+    ///
+    pub fn step(&self, by: i32) -> Cr {
+        let index = (self.index() as i32 + by).clamp(0, Cr::MAX.index() as i32);
+        Cr::all().nth(index as usize).unwrap_or(Cr::MAX)
+    }
+
+    ///
+    /// Challenge rating closest to `value`, the lower on a tie.
+    ///
+    pub fn nearest(value: f32) -> Cr {
+        Cr::all()
+            .min_by(|a, b| {
+                (a.value() - value)
+                    .abs()
+                    .total_cmp(&(b.value() - value).abs())
+            })
+            .unwrap_or(Cr::Zero)
+    }
+
+    ///
+    /// As a number (e.g. 0.25 for CR 1/4).
+    ///
+    pub fn value(&self) -> f32 {
+        match self {
+            Cr::Zero => 0.0,
+            Cr::Eighth => 0.125,
+            Cr::Quarter => 0.25,
+            Cr::Half => 0.5,
+            Cr::Whole(n) => *n as f32,
+        }
+    }
+
+    ///
+    /// Experience points for defeating a monster of this CR
+    /// (10 at CR 0, for monsters with any effective attacks).
+    ///
+    pub fn xp(&self) -> usize {
+        match self {
+            Cr::Zero => 10,
+            Cr::Eighth => 25,
+            Cr::Quarter => 50,
+            Cr::Half => 100,
+            Cr::Whole(n) => XP[(*n).clamp(1, 30) as usize - 1],
+        }
+    }
+
+    ///
+    /// Proficiency bonus: +2 up to CR 4, and another +1 every four CRs.
+    ///
+    pub fn proficiency_bonus(&self) -> i32 {
+        match self {
+            Cr::Whole(n) if *n > 4 => 2 + (*n as i32 - 1) / 4,
+            _ => 2,
+        }
+    }
+}
+
+impl std::fmt::Display for Cr {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Cr::Zero => write!(f, "0"),
+            Cr::Eighth => write!(f, "1/8"),
+            Cr::Quarter => write!(f, "1/4"),
+            Cr::Half => write!(f, "1/2"),
+            Cr::Whole(n) => write!(f, "{n}"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Cr;
+
+    #[test]
+    fn tables() {
+        assert_eq!(Cr::all().count(), 34);
+        assert!(Cr::Half < Cr::Whole(1) && Cr::Whole(2) < Cr::Whole(10));
+
+        let bonuses = [Cr::Zero, Cr::Whole(4), Cr::Whole(5), Cr::Whole(17), Cr::MAX]
+            .map(|cr| cr.proficiency_bonus());
+        assert_eq!(bonuses, [2, 2, 3, 6, 9]);
+
+        assert_eq!(Cr::Quarter.xp(), 50);
+        assert_eq!(Cr::Whole(17).xp(), 18_000);
+        assert_eq!(Cr::Eighth.to_string(), "1/8");
+
+        assert_eq!(Cr::Half.step(2), Cr::Whole(2));
+        assert_eq!(Cr::Eighth.step(-3), Cr::Zero);
+        assert_eq!(Cr::Whole(29).step(4), Cr::MAX);
+        assert_eq!(Cr::nearest(1.25), Cr::Whole(1));
+        assert_eq!(Cr::nearest(3.5), Cr::Whole(3));
+        assert_eq!(Cr::nearest(0.3), Cr::Quarter);
+    }
+}
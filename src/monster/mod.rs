@@ -0,0 +1,702 @@
+//!
+//! Monster stat blocks, built on [Creature].
+//!
+//! ### Examples
+//! ***
+//! Checking a stat block against the engine:
+//! ```
+//! use xander::{
+//!     ability::{skills::Stealth, Checks},
+//!     creature::proficiency::IntoExpertise,
+//!     monster::{self, Cr},
+//! };
+//!
+//! let mut goblin = monster::goblin();
+//! assert_eq!(goblin.cr(), Cr::Quarter);
+//! assert_eq!(goblin.xp(), 50);
+//!
+//! // Stealth is printed at +6: DEX +2 and double proficiency.
+//! assert_eq!(goblin.verify()[0].computed, 4);
+//!
+//! goblin.creature_mut().proficiencies().insert(Checks(Stealth).expertise());
+//! assert!(goblin.verify().is_empty());
+//! ```
+//!
+
+mod calculator;
+mod challenge;
+
+pub use calculator::{expected, Breakdown, Profile};
+pub use challenge::Cr;
+
+use std::hash::Hash;
+
+use crate::{
+    ability::{
+        skills::{Perception, Stealth},
+        Ability, Charisma, Check, Checks, Constitution, Dexterity, Intelligence, Save, Saves,
+        Strength, Wisdom, CHECKS, SAVES,
+    },
+    creature::{
+        effect::RollKind,
+        hooks::Outcome,
+        proficiency::IntoExpertise,
+        resource::{Resource, ResourceError},
+        rest::Recharge,
+        Creature,
+    },
+    damage::DamageType,
+    dice::{Rolls, D10, D6, D8},
+    identity::Identity,
+    item::weapon::DamageRoll,
+    origin::Size,
+};
+
+///
+/// Resource tracking legendary actions, regained every turn.
+///
+pub const LEGENDARY_ACTIONS: &str = "5E::RESOURCE::LEGENDARY_ACTIONS";
+
+///
+/// Resource tracking Legendary Resistance, regained every day.
+///
+pub const LEGENDARY_RESISTANCE: &str = "5E::RESOURCE::LEGENDARY_RESISTANCE";
+
+///
+/// *Fire Breath* of red dragons (Recharge 5–6).
+///
+pub const FIRE_BREATH: &str = "5E::RESOURCE::FIRE_BREATH";
+
+///
+/// A weapon attack, as printed (to hit, and damage by type).
+///
+/// Made with Strength, unless [Attack::using] says otherwise.
+///
+#[derive(Debug, Clone)]
+pub struct Attack {
+    name: &'static str,
+    bonus: i32,
+    ability: &'static dyn Ability,
+    save: Option<(&'static dyn Ability, i32)>,
+    damage: Vec<(DamageRoll, DamageType)>,
+}
+
+impl Attack {
+    pub fn new(name: &'static str, bonus: i32) -> Self {
+        Self {
+            name,
+            bonus,
+            ability: &Strength,
+            save: None,
+            damage: vec![],
+        }
+    }
+
+    ///
+    /// An attack the target makes a saving throw against instead
+    /// of being rolled against (e.g. a spit of acid), for half
+    /// damage on a success.
+    ///
+    pub fn save(name: &'static str, ability: &'static dyn Ability, dc: i32) -> Self {
+        Self {
+            save: Some((ability, dc)),
+            ..Self::new(name, 0)
+        }
+    }
+
+    ///
+    /// The ability the attack is made with (e.g. DEX, for finesse or ranged weapons).
+    ///
+    pub fn using(mut self, ability: &'static dyn Ability) -> Self {
+        self.ability = ability;
+        self
+    }
+
+    pub fn damage(mut self, roll: DamageRoll, kind: DamageType) -> Self {
+        self.damage.push((roll, kind));
+        self
+    }
+
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    pub fn bonus(&self) -> i32 {
+        self.bonus
+    }
+
+    pub fn ability(&self) -> &'static dyn Ability {
+        self.ability
+    }
+
+    ///
+    /// Ability and DC of the saving throw, if it calls for one.
+    ///
+    pub fn saving_throw(&self) -> Option<(&'static dyn Ability, i32)> {
+        self.save
+    }
+
+    pub fn damage_rolls(&self) -> &[(DamageRoll, DamageType)] {
+        &self.damage
+    }
+
+    ///
+    /// Roll every damage die of this attack, by type.
+    ///
+    pub fn roll_damage(&self) -> Vec<(Rolls, DamageType)> {
+        self.damage
+            .iter()
+            .map(|(roll, kind)| (roll(), *kind))
+            .collect()
+    }
+
+    ///
+    /// Average damage of this attack, on a hit.
+    ///
+    pub fn expected_damage(&self) -> f32 {
+        self.damage.iter().map(|(roll, _)| roll().expected()).sum()
+    }
+}
+
+///
+/// An action taken at the end of another creature's turn.
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LegendaryAction {
+    pub name: &'static str,
+    ///
+    /// Legendary actions it takes up.
+    ///
+    pub cost: usize,
+}
+
+///
+/// A printed bonus, and what the engine makes of it.
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Mismatch {
+    pub id: &'static str,
+    pub printed: i32,
+    pub computed: i32,
+}
+
+///
+/// A bonus as printed in the stat block.
+///
+#[derive(Debug)]
+struct Printed {
+    category: &'static str,
+    id: &'static str,
+    ability: Box<dyn Ability>,
+    bonus: i32,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MonsterError {
+    ///
+    /// No such attack, or legendary action.
+    ///
+    Unknown(&'static str),
+    ///
+    /// Not enough legendary actions left.
+    ///
+    Resource(ResourceError),
+}
+
+impl From<ResourceError> for MonsterError {
+    fn from(err: ResourceError) -> Self {
+        Self::Resource(err)
+    }
+}
+
+///
+/// A monster's stat block.
+///
+/// Skills, saves and attacks listed with [Monster::skill], [Monster::save]
+/// and [Monster::attack] are taken as proficient: [Monster::verify] points
+/// out any whose printed bonus the engine doesn't agree with (e.g. expertise).
+///
+#[derive(Debug)]
+pub struct Monster {
+    name: &'static str,
+    creature: Creature,
+    cr: Cr,
+    attacks: Vec<Attack>,
+    multiattack: Vec<(&'static str, usize)>,
+    legendary: Vec<LegendaryAction>,
+    lair: Vec<&'static str>,
+    printed: Vec<Printed>,
+}
+
+impl Monster {
+    pub fn new(name: &'static str, cr: Cr) -> Self {
+        let mut creature = Creature::default();
+        creature.set_proficiency_bonus(Some(cr.proficiency_bonus()));
+
+        Self {
+            name,
+            creature,
+            cr,
+            attacks: vec![],
+            multiattack: vec![],
+            legendary: vec![],
+            lair: vec![],
+            printed: vec![],
+        }
+    }
+
+    ///
+    /// STR, DEX, CON, INT, WIS and CHA scores, in that order.
+    ///
+    pub fn scores(mut self, scores: [usize; 6]) -> Self {
+        let abilities = [
+            Strength.id(),
+            Dexterity.id(),
+            Constitution.id(),
+            Intelligence.id(),
+            Wisdom.id(),
+            Charisma.id(),
+        ];
+        self.creature
+            .stats()
+            .extend(abilities.into_iter().zip(scores));
+        self
+    }
+
+    pub fn ac(mut self, ac: i32) -> Self {
+        self.creature.set_armor_class(Some(ac));
+        self
+    }
+
+    pub fn hp(mut self, hp: usize) -> Self {
+        self.creature.set_max_hp(Some(hp));
+        self
+    }
+
+    pub fn speed(mut self, speed: u32) -> Self {
+        self.creature.set_speed(speed);
+        self
+    }
+
+    pub fn size(mut self, size: Size) -> Self {
+        self.creature.set_size(size);
+        self
+    }
+
+    ///
+    /// A proficient skill, and its printed bonus.
+    ///
+    pub fn skill<C>(mut self, skill: C, bonus: i32) -> Self
+    where
+        C: Check + Hash + Eq + Clone + 'static,
+    {
+        self.printed.push(Printed {
+            category: CHECKS,
+            id: skill.id(),
+            ability: C::base(),
+            bonus,
+        });
+        self.creature.proficiencies().insert(Checks(skill));
+        self
+    }
+
+    ///
+    /// A proficient saving throw, and its printed bonus.
+    ///
+    pub fn save<S>(mut self, ability: S, bonus: i32) -> Self
+    where
+        S: Save + Ability + Hash + Eq + Clone + 'static,
+    {
+        self.printed.push(Printed {
+            category: SAVES,
+            id: ability.id(),
+            ability: S::base(),
+            bonus,
+        });
+        self.creature.proficiencies().insert(Saves(ability));
+        self
+    }
+
+    pub fn attack(mut self, attack: Attack) -> Self {
+        self.attacks.push(attack);
+        self
+    }
+
+    ///
+    /// Attacks (by name) made with the Multiattack action.
+    ///
+    pub fn multiattack(mut self, attacks: &[(&'static str, usize)]) -> Self {
+        self.multiattack = attacks.to_vec();
+        self
+    }
+
+    ///
+    /// Legendary actions per round, and what they can be spent on.
+    ///
+    pub fn legendary_actions(mut self, per_round: usize, actions: &[LegendaryAction]) -> Self {
+        self.legendary = actions.to_vec();
+        self.creature.add_resource(Resource::fixed(
+            LEGENDARY_ACTIONS,
+            per_round,
+            Recharge::Turn,
+        ));
+        self
+    }
+
+    ///
+    /// Failed saves that can be turned into successes, per day.
+    ///
+    /// Each failed saving throw uses one up, while there are any left.
+    ///
+    pub fn legendary_resistance(mut self, per_day: usize) -> Self {
+        self.creature.add_resource(Resource::fixed(
+            LEGENDARY_RESISTANCE,
+            per_day,
+            Recharge::LongRest,
+        ));
+        self.creature
+            .hooks()
+            .after_result(LEGENDARY_RESISTANCE, |ent, kind, _, outcome| {
+                match ent.resource(LEGENDARY_RESISTANCE) {
+                    Some(uses)
+                        if kind == RollKind::Save && !outcome.success() && uses.left(ent) > 0 =>
+                    {
+                        uses.spend();
+                        Outcome {
+                            forced: Some(true),
+                            ..outcome
+                        }
+                    }
+                    _ => outcome,
+                }
+            });
+        self
+    }
+
+    ///
+    /// Lair actions, taken on initiative count 20.
+    ///
+    pub fn lair_actions(mut self, actions: &[&'static str]) -> Self {
+        self.lair = actions.to_vec();
+        self
+    }
+
+    pub fn resistant(mut self, kind: DamageType) -> Self {
+        self.creature.defenses_mut().resist(kind);
+        self
+    }
+
+    pub fn immune(mut self, kind: DamageType) -> Self {
+        self.creature.defenses_mut().immune(kind);
+        self
+    }
+
+    pub fn vulnerable(mut self, kind: DamageType) -> Self {
+        self.creature.defenses_mut().vulnerable(kind);
+        self
+    }
+
+    ///
+    /// Finish the stat block off with a recharge
+    /// ability, or anything else on the creature.
+    ///
+    pub fn with(mut self, f: impl FnOnce(&mut Creature)) -> Self {
+        f(&mut self.creature);
+        self
+    }
+
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    pub fn cr(&self) -> Cr {
+        self.cr
+    }
+
+    pub fn xp(&self) -> usize {
+        self.cr.xp()
+    }
+
+    pub fn attacks(&self) -> &[Attack] {
+        &self.attacks
+    }
+
+    pub fn find_attack(&self, name: &str) -> Option<&Attack> {
+        self.attacks.iter().find(|a| a.name == name)
+    }
+
+    ///
+    /// Attacks made on its turn: every attack of its
+    /// Multiattack, or else its first attack.
+    ///
+    pub fn round(&self) -> Vec<&Attack> {
+        match self.multiattack.as_slice() {
+            [] => self.attacks.iter().take(1).collect(),
+            multi => multi
+                .iter()
+                .filter_map(|(name, n)| self.find_attack(name).map(|a| (a, *n)))
+                .flat_map(|(a, n)| std::iter::repeat_n(a, n))
+                .collect(),
+        }
+    }
+
+    pub fn legendary(&self) -> &[LegendaryAction] {
+        &self.legendary
+    }
+
+    ///
+    /// Take a legendary action, returning how many are left.
+    ///
+    pub fn legendary_action(&mut self, name: &'static str) -> Result<usize, MonsterError> {
+        let action = self
+            .legendary
+            .iter()
+            .find(|a| a.name == name)
+            .ok_or(MonsterError::Unknown(name))?;
+
+        match self.creature.uses(LEGENDARY_ACTIONS) {
+            Some((left, _)) if left >= action.cost => {
+                let mut left = left;
+                for _ in 0..action.cost {
+                    left = self.creature.spend(LEGENDARY_ACTIONS)?;
+                }
+                Ok(left)
+            }
+            Some(_) => Err(ResourceError::Exhausted(LEGENDARY_ACTIONS).into()),
+            None => Err(ResourceError::Unknown(LEGENDARY_ACTIONS).into()),
+        }
+    }
+
+    ///
+    /// Use Legendary Resistance to succeed on a failed save,
+    /// returning how many uses are left.
+    ///
+    pub fn resist(&mut self) -> Result<usize, ResourceError> {
+        self.creature.spend(LEGENDARY_RESISTANCE)
+    }
+
+    pub fn lair(&self) -> &[&'static str] {
+        &self.lair
+    }
+
+    ///
+    /// Printed skill, save and attack bonuses the engine
+    /// works out differently, from scores and proficiencies.
+    ///
+    /// Attacks go by their name, those calling for a save are left out.
+    ///
+    pub fn verify(&self) -> Vec<Mismatch> {
+        let printed = self.printed.iter().map(|p| Mismatch {
+            id: p.id,
+            printed: p.bonus,
+            computed: self.creature.bonus_id(p.category, p.id, p.ability.as_ref()),
+        });
+        let rolled = self.attacks.iter().filter(|a| a.save.is_none());
+        let attacks = rolled.map(|a| Mismatch {
+            id: a.name,
+            printed: a.bonus,
+            computed: self.creature.modifier_id(a.ability.id()).unwrap_or(0)
+                + self.creature.proficency_modifier(),
+        });
+
+        printed
+            .chain(attacks)
+            .filter(|m| m.printed != m.computed)
+            .collect()
+    }
+
+    pub fn creature(&self) -> &Creature {
+        &self.creature
+    }
+
+    pub fn creature_mut(&mut self) -> &mut Creature {
+        &mut self.creature
+    }
+
+    pub fn into_creature(self) -> Creature {
+        self.creature
+    }
+}
+
+///
+/// *Goblin*: small, nimble and stealthy.
+///
+pub fn goblin() -> Monster {
+    Monster::new("Goblin", Cr::Quarter)
+        .size(Size::Small)
+        .scores([8, 14, 10, 10, 8, 8])
+        .ac(15)
+        .hp(7)
+        .speed(30)
+        .skill(Stealth, 6)
+        .attack(
+            Attack::new("Scimitar", 4)
+                .using(&Dexterity)
+                .damage(|| D6(1) + 2, DamageType::Slashing),
+        )
+        .attack(
+            Attack::new("Shortbow", 4)
+                .using(&Dexterity)
+                .damage(|| D6(1) + 2, DamageType::Piercing),
+        )
+}
+
+///
+/// *Adult Red Dragon*, with its lair.
+///
+pub fn adult_red_dragon() -> Monster {
+    Monster::new("Adult Red Dragon", Cr::Whole(17))
+        .size(Size::Huge)
+        .scores([27, 10, 25, 16, 13, 21])
+        .ac(19)
+        .hp(256)
+        .speed(40)
+        .save(Dexterity, 6)
+        .save(Constitution, 13)
+        .save(Wisdom, 7)
+        .save(Charisma, 11)
+        .skill(Perception, 13)
+        .skill(Stealth, 6)
+        .immune(DamageType::Fire)
+        .attack(
+            Attack::new("Bite", 14)
+                .damage(|| D10(2) + 8, DamageType::Piercing)
+                .damage(|| D6(2), DamageType::Fire),
+        )
+        .attack(Attack::new("Claw", 14).damage(|| D6(2) + 8, DamageType::Slashing))
+        .attack(Attack::new("Tail", 14).damage(|| D8(2) + 8, DamageType::Bludgeoning))
+        .multiattack(&[("Bite", 1), ("Claw", 2)])
+        .legendary_actions(
+            3,
+            &[
+                LegendaryAction {
+                    name: "Detect",
+                    cost: 1,
+                },
+                LegendaryAction {
+                    name: "Tail Attack",
+                    cost: 1,
+                },
+                LegendaryAction {
+                    name: "Wing Attack",
+                    cost: 2,
+                },
+            ],
+        )
+        .legendary_resistance(3)
+        .lair_actions(&[
+            "Magma erupts from a point on the ground",
+            "A tremor shakes the lair",
+            "Volcanic gases form a cloud",
+        ])
+        .with(|ent| {
+            ent.add_resource(Resource::fixed(FIRE_BREATH, 1, Recharge::Roll(5)));
+            ent.proficiencies().insert(Checks(Perception).expertise());
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        ability::{skills::Stealth, Dexterity, SAVES},
+        creature::{context::CheckContext, resource::ResourceError},
+        damage::DamageType,
+        identity::Identity,
+    };
+
+    use super::{adult_red_dragon, goblin, Attack, Cr, Mismatch, MonsterError, FIRE_BREATH};
+
+    #[test]
+    fn stat_blocks() {
+        let goblin = goblin();
+        assert_eq!(goblin.creature().max_hp(), 7);
+        assert_eq!(goblin.creature().armor_class(), 15);
+        assert_eq!(goblin.creature().proficency_modifier(), 2);
+        assert_eq!(goblin.round().len(), 1);
+
+        let mismatch = goblin.verify();
+        assert_eq!(mismatch.len(), 1);
+        assert_eq!((mismatch[0].id, mismatch[0].printed), (Stealth.id(), 6));
+
+        // STR 8, proficient: +1, not +4.
+        let mismatch = super::goblin().attack(Attack::new("Club", 4)).verify();
+        assert_eq!(
+            mismatch[1],
+            Mismatch {
+                id: "Club",
+                printed: 4,
+                computed: 1,
+            }
+        );
+
+        let dragon = adult_red_dragon();
+        assert_eq!(dragon.cr(), Cr::Whole(17));
+        assert_eq!(dragon.creature().proficency_modifier(), 6);
+        assert_eq!(dragon.xp(), 18_000);
+        assert!(dragon.verify().is_empty());
+        // DEX 10, proficient: the printed +6.
+        assert_eq!(
+            dragon
+                .creature()
+                .bonus_id(SAVES, Dexterity.id(), &Dexterity),
+            6
+        );
+
+        let names = dragon.round().iter().map(|a| a.name()).collect::<Vec<_>>();
+        assert_eq!(names, ["Bite", "Claw", "Claw"]);
+        assert_eq!(
+            dragon
+                .creature()
+                .defenses()
+                .damage_taken(30, DamageType::Fire),
+            0
+        );
+        assert_eq!(dragon.lair().len(), 3);
+    }
+
+    #[test]
+    fn legendary() {
+        let mut dragon = adult_red_dragon();
+
+        assert_eq!(dragon.legendary_action("Wing Attack"), Ok(1));
+        assert_eq!(
+            dragon.legendary_action("Wing Attack"),
+            Err(MonsterError::Resource(ResourceError::Exhausted(
+                super::LEGENDARY_ACTIONS
+            )))
+        );
+        assert_eq!(
+            dragon.legendary_action("Roar"),
+            Err(MonsterError::Unknown("Roar"))
+        );
+        assert_eq!(dragon.legendary_action("Detect"), Ok(0));
+
+        dragon.creature_mut().start_turn();
+        assert_eq!(
+            dragon.creature().uses(super::LEGENDARY_ACTIONS),
+            Some((3, 3))
+        );
+
+        (0..3).for_each(|_| {
+            dragon.resist().unwrap();
+        });
+        assert!(dragon.resist().is_err());
+
+        assert_eq!(dragon.creature_mut().spend(FIRE_BREATH), Ok(0));
+
+        // Failed saves use Legendary Resistance up on their own.
+        let dragon = adult_red_dragon();
+        let ctx = CheckContext::default();
+        for left in [2, 1, 0] {
+            assert!(dragon
+                .creature()
+                .save_against(Dexterity, ctx.clone(), 40)
+                .success());
+            assert_eq!(
+                dragon.creature().uses(super::LEGENDARY_RESISTANCE),
+                Some((left, 3))
+            );
+        }
+        assert!(!dragon.creature().save_against(Dexterity, ctx, 40).success());
+    }
+}
@@ -0,0 +1,83 @@
+use xander_macros::identify;
+
+use crate::{
+    ability::{
+        skills::{Arcana, Athletics, Deception, History, Insight, Intimidation, Religion, Stealth},
+        Checks,
+    },
+    creature::proficiency::Offer,
+    feature::Feature,
+    identity::Identity,
+    item::{
+        tool::{LandVehicles, ThievesTools},
+        Tools,
+    },
+};
+
+pub trait Background: Identity {
+    ///
+    /// Skill and tool proficiencies.
+    ///
+    /// Languages and gaming sets of choice are left to the caller.
+    ///
+    fn proficiencies(&self) -> Vec<Offer>;
+
+    ///
+    /// Background features, applied as features.
+    ///
+    fn traits(&self) -> Vec<Box<dyn Feature>> {
+        vec![]
+    }
+}
+
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
+pub struct Acolyte;
+
+identify!(Acolyte, "5E::BACKGROUND::ACOLYTE");
+
+impl Background for Acolyte {
+    fn proficiencies(&self) -> Vec<Offer> {
+        vec![Offer::new(Checks(Insight)), Offer::new(Checks(Religion))]
+    }
+}
+
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
+pub struct Criminal;
+
+identify!(Criminal, "5E::BACKGROUND::CRIMINAL");
+
+impl Background for Criminal {
+    fn proficiencies(&self) -> Vec<Offer> {
+        vec![
+            Offer::new(Checks(Deception)),
+            Offer::new(Checks(Stealth)),
+            Offer::new(Tools(ThievesTools)),
+        ]
+    }
+}
+
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
+pub struct Sage;
+
+identify!(Sage, "5E::BACKGROUND::SAGE");
+
+impl Background for Sage {
+    fn proficiencies(&self) -> Vec<Offer> {
+        vec![Offer::new(Checks(Arcana)), Offer::new(Checks(History))]
+    }
+}
+
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
+pub struct Soldier;
+
+identify!(Soldier, "5E::BACKGROUND::SOLDIER");
+
+impl Background for Soldier {
+    fn proficiencies(&self) -> Vec<Offer> {
+        vec![
+            Offer::new(Checks(Athletics)),
+            Offer::new(Checks(Intimidation)),
+            Offer::new(Tools(LandVehicles)),
+        ]
+    }
+}
@@ -0,0 +1,230 @@
+//!
+//! Where a creature comes from: its race and background.
+//!
+//! Both are applied once, at build time. A race or background
+//! offering a skill the creature already has (say, from its class)
+//! asks for a replacement skill instead, picked by the caller.
+//!
+//! ### Examples
+//! ***
+//! A dwarven soldier, who already picked Athletics as a fighter:
+//! ```
+//! use xander::{
+//!     ability::{skills::{Athletics, Perception, Survival}, Constitution, Strength},
+//!     class::Fighter,
+//!     creature::Creature,
+//!     identity::Identity,
+//!     origin::{HillDwarf, OriginError, Soldier},
+//! };
+//!
+//! let mut ent = Creature::default();
+//! ent.stats().extend([(Strength.id(), 15), (Constitution.id(), 14)]);
+//!
+//! ent.set_race(&HillDwarf, &[]).unwrap();
+//! ent.add_level(&Fighter, &[Athletics.id(), Perception.id()]).unwrap();
+//!
+//! assert_eq!(
+//!     ent.set_background(&Soldier, &[]),
+//!     Err(OriginError::Conflicts(vec![Athletics.id()]))
+//! );
+//! ent.set_background(&Soldier, &[Survival.id()]).unwrap();
+//!
+//! assert_eq!(ent.score::<Constitution>(Constitution), Some(16));
+//! assert_eq!(ent.speed(), 25);
+//! assert_eq!(ent.senses().darkvision, Some(60));
+//! ```
+//!
+
+mod background;
+mod race;
+
+pub use background::{Acolyte, Background, Criminal, Sage, Soldier};
+pub use race::{
+    Brave, DwarvenResilience, FeyAncestry, HalflingLuck, HighElf, HillDwarf, Human,
+    LightfootHalfling, Race, CHARMED, FRIGHTENED, POISON,
+};
+
+use crate::{
+    ability::{skills, CHECKS},
+    creature::proficiency::Offer,
+};
+
+///
+/// How much space a creature takes up.
+///
+#[derive(Debug, Default, Clone, Copy, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Size {
+    Tiny,
+    Small,
+    #[default]
+    Medium,
+    Large,
+    Huge,
+    Gargantuan,
+}
+
+///
+/// Special senses, by range in feet.
+///
+#[derive(Debug, Default, Clone, Copy, Hash, PartialEq, Eq)]
+pub struct Senses {
+    pub darkvision: Option<u32>,
+    pub blindsight: Option<u32>,
+    pub tremorsense: Option<u32>,
+    pub truesight: Option<u32>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum OriginError {
+    ///
+    /// A race (or background) was already chosen.
+    ///
+    AlreadySet(&'static str),
+    ///
+    /// Skills offered that are already had,
+    /// and need as many replacements picked.
+    ///
+    Conflicts(Vec<&'static str>),
+    ///
+    /// Replacement isn't a skill, is already had,
+    /// or was picked twice.
+    ///
+    InvalidReplacement(&'static str),
+}
+
+///
+/// Pick replacements for every conflicting skill in `offers`,
+/// returning what should be granted instead.
+///
+/// `has` tells whether a skill (by id) is already had.
+///
+pub fn resolve(
+    offers: Vec<Offer>,
+    replacements: &[&'static str],
+    has: impl Fn(&str) -> bool,
+) -> Result<Vec<Offer>, OriginError> {
+    let (conflicts, mut granted) = offers
+        .into_iter()
+        .partition::<Vec<_>, _>(|o| o.category() == CHECKS && has(o.id()));
+
+    if conflicts.len() != replacements.len() {
+        return Err(OriginError::Conflicts(
+            conflicts.iter().map(Offer::id).collect(),
+        ));
+    }
+
+    let mut skills = skills::offers();
+    for id in replacements {
+        let taken = has(id) || granted.iter().any(|o| o.id() == *id);
+        let i = skills
+            .iter()
+            .position(|o| o.id() == *id)
+            .filter(|_| !taken)
+            .ok_or(OriginError::InvalidReplacement(id))?;
+
+        granted.push(skills.swap_remove(i));
+    }
+
+    Ok(granted)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        ability::{
+            skills::{Arcana, History, Insight, Perception, Religion, Stealth},
+            Checks, Dexterity, Intelligence, Strength, Wisdom,
+        },
+        creature::{context::CheckContext, proficiency::Source, Creature},
+        dice::D20,
+        identity::Identity,
+        item::weapon::{Longsword, Weapon},
+        language::{Elvish, Languages},
+    };
+
+    use super::{Acolyte, HighElf, Human, LightfootHalfling, OriginError, Sage, Size, CHARMED};
+
+    #[test]
+    fn races() {
+        let mut ent = Creature::default();
+        ent.stats()
+            .extend([(Dexterity.id(), 19), (Intelligence.id(), 12)]);
+        ent.set_race(&HighElf, &[]).unwrap();
+
+        // Capped at 20.
+        assert_eq!(ent.score::<Dexterity>(Dexterity), Some(20));
+        assert_eq!(ent.score::<Intelligence>(Intelligence), Some(13));
+        assert_eq!(ent.speed(), 30);
+        assert_eq!(ent.size(), Size::Medium);
+        assert!(ent.proficient(Languages(Elvish)).is_some());
+        assert!(Longsword.proficient(&ent));
+        assert_eq!(
+            ent.explain(Checks(Perception))[0].source(),
+            Source::Race(HighElf.id())
+        );
+
+        let charmed = CheckContext::default().tag(CHARMED);
+        assert_eq!(ent.save_in(Wisdom, charmed)[D20].len(), 2);
+
+        assert_eq!(
+            ent.set_race(&Human, &[]),
+            Err(OriginError::AlreadySet(HighElf.id()))
+        );
+
+        let mut halfling = Creature::default();
+        halfling.set_race(&LightfootHalfling, &[]).unwrap();
+        assert_eq!(halfling.size(), Size::Small);
+        assert_eq!(halfling.senses().darkvision, None);
+
+        let mut scout = Creature::default();
+        scout.proficiencies().insert(Checks(Perception));
+        assert_eq!(
+            scout.set_race(&HighElf, &[]),
+            Err(OriginError::Conflicts(vec![Perception.id()]))
+        );
+        assert!(scout.race().is_none());
+
+        scout.set_race(&HighElf, &[Stealth.id()]).unwrap();
+        assert_eq!(
+            scout.explain(Checks(Stealth))[0].source(),
+            Source::Race(HighElf.id())
+        );
+    }
+
+    #[test]
+    fn backgrounds() {
+        let mut ent = Creature::default();
+        ent.proficiencies().insert(Checks(Insight));
+
+        assert_eq!(
+            ent.set_background(&Acolyte, &[]),
+            Err(OriginError::Conflicts(vec![Insight.id()]))
+        );
+        // Already had, offered by the background, or not a skill at all.
+        for invalid in [Insight.id(), Religion.id(), Strength.id()] {
+            assert_eq!(
+                ent.set_background(&Acolyte, &[invalid]),
+                Err(OriginError::InvalidReplacement(invalid))
+            );
+        }
+        assert!(ent.background().is_none());
+
+        ent.set_background(&Acolyte, &[Stealth.id()]).unwrap();
+        assert_eq!(
+            ent.explain(Checks(Stealth))[0].source(),
+            Source::Background(Acolyte.id())
+        );
+        assert!(ent.proficient(Checks(Religion)).is_some());
+        assert_eq!(ent.explain(Checks(Insight)).len(), 1);
+
+        assert_eq!(
+            ent.set_background(&Sage, &[]),
+            Err(OriginError::AlreadySet(Acolyte.id()))
+        );
+
+        let mut sage = Creature::default();
+        sage.set_background(&Sage, &[]).unwrap();
+        assert!(sage.proficient(Checks(Arcana)).is_some());
+        assert!(sage.proficient(Checks(History)).is_some());
+    }
+}
@@ -0,0 +1,265 @@
+use xander_macros::identify;
+
+use crate::{
+    ability::{
+        skills::Perception, Charisma, Checks, Constitution, Dexterity, Intelligence, Strength,
+        Wisdom,
+    },
+    creature::{context::Edge, proficiency::Offer, Creature},
+    dice::D20,
+    feature::Feature,
+    identity::Identity,
+    item::{
+        weapon::{
+            Battleaxe, Handaxe, LightHammer, Longbow, Longsword, Shortbow, Shortsword, Warhammer,
+        },
+        Weapons,
+    },
+    language::{Common, Dwarvish, Elvish, Halfling, Languages},
+};
+
+use super::Size;
+
+///
+/// Tag for rolls made against poison.
+///
+pub const POISON: &str = "5E::TAG::POISON";
+
+///
+/// Tag for rolls made against being charmed.
+///
+pub const CHARMED: &str = "5E::TAG::CHARMED";
+
+///
+/// Tag for rolls made against being frightened.
+///
+pub const FRIGHTENED: &str = "5E::TAG::FRIGHTENED";
+
+pub trait Race: Identity {
+    ///
+    /// Ability score increases, by ability id.
+    ///
+    fn ability_scores(&self) -> Vec<(&'static str, usize)>;
+
+    ///
+    /// Base walking speed, in feet.
+    ///
+    fn speed(&self) -> u32 {
+        30
+    }
+
+    fn size(&self) -> Size {
+        Size::Medium
+    }
+
+    ///
+    /// Range of darkvision, in feet.
+    ///
+    fn darkvision(&self) -> Option<u32> {
+        None
+    }
+
+    ///
+    /// Skills, weapons, tools and languages.
+    ///
+    fn proficiencies(&self) -> Vec<Offer>;
+
+    ///
+    /// Racial traits, applied as features.
+    ///
+    fn traits(&self) -> Vec<Box<dyn Feature>> {
+        vec![]
+    }
+}
+
+///
+/// *Dwarven Resilience*: advantage on saves against poison.
+///
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
+pub struct DwarvenResilience;
+
+identify!(DwarvenResilience, "5E::TRAIT::DWARVEN_RESILIENCE");
+
+impl Feature for DwarvenResilience {
+    fn grant(&self, ent: &mut Creature) {
+        ent.add_edge(Edge::advantage(self.id(), |_, ctx| ctx.tagged(POISON)));
+    }
+}
+
+///
+/// *Fey Ancestry*: advantage on saves against being charmed.
+///
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
+pub struct FeyAncestry;
+
+identify!(FeyAncestry, "5E::TRAIT::FEY_ANCESTRY");
+
+impl Feature for FeyAncestry {
+    fn grant(&self, ent: &mut Creature) {
+        ent.add_edge(Edge::advantage(self.id(), |_, ctx| ctx.tagged(CHARMED)));
+    }
+}
+
+///
+/// *Brave*: advantage on saves against being frightened.
+///
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
+pub struct Brave;
+
+identify!(Brave, "5E::TRAIT::BRAVE");
+
+impl Feature for Brave {
+    fn grant(&self, ent: &mut Creature) {
+        ent.add_edge(Edge::advantage(self.id(), |_, ctx| ctx.tagged(FRIGHTENED)));
+    }
+}
+
+///
+/// *Lucky* (halfling): a 1 on the d20 of an attack,
+/// check or save is rerolled, and the new roll kept.
+///
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
+pub struct HalflingLuck;
+
+identify!(HalflingLuck, "5E::TRAIT::HALFLING_LUCK");
+
+impl Feature for HalflingLuck {
+    fn grant(&self, ent: &mut Creature) {
+        ent.hooks().after_roll(self.id(), |_, _, _, rolls| {
+            if !rolls[D20].iter().any(|r| r.value() == 1) {
+                return rolls;
+            }
+
+            let d20s = rolls[D20]
+                .iter()
+                .map(|r| match r.value() {
+                    1 => D20()[D20][0].clone(),
+                    _ => r.clone(),
+                })
+                .collect::<Vec<_>>();
+            rolls.replace(&D20, d20s)
+        });
+    }
+}
+
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
+pub struct HillDwarf;
+
+identify!(HillDwarf, "5E::RACE::HILL_DWARF");
+
+impl Race for HillDwarf {
+    fn ability_scores(&self) -> Vec<(&'static str, usize)> {
+        vec![(Constitution.id(), 2), (Wisdom.id(), 1)]
+    }
+
+    fn speed(&self) -> u32 {
+        25
+    }
+
+    fn darkvision(&self) -> Option<u32> {
+        Some(60)
+    }
+
+    fn proficiencies(&self) -> Vec<Offer> {
+        vec![
+            Offer::new(Weapons(Battleaxe)),
+            Offer::new(Weapons(Handaxe)),
+            Offer::new(Weapons(LightHammer)),
+            Offer::new(Weapons(Warhammer)),
+            Offer::new(Languages(Common)),
+            Offer::new(Languages(Dwarvish)),
+        ]
+    }
+
+    fn traits(&self) -> Vec<Box<dyn Feature>> {
+        vec![Box::new(DwarvenResilience)]
+    }
+}
+
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
+pub struct HighElf;
+
+identify!(HighElf, "5E::RACE::HIGH_ELF");
+
+impl Race for HighElf {
+    fn ability_scores(&self) -> Vec<(&'static str, usize)> {
+        vec![(Dexterity.id(), 2), (Intelligence.id(), 1)]
+    }
+
+    fn darkvision(&self) -> Option<u32> {
+        Some(60)
+    }
+
+    fn proficiencies(&self) -> Vec<Offer> {
+        vec![
+            Offer::new(Checks(Perception)),
+            Offer::new(Weapons(Longsword)),
+            Offer::new(Weapons(Shortsword)),
+            Offer::new(Weapons(Shortbow)),
+            Offer::new(Weapons(Longbow)),
+            Offer::new(Languages(Common)),
+            Offer::new(Languages(Elvish)),
+        ]
+    }
+
+    fn traits(&self) -> Vec<Box<dyn Feature>> {
+        vec![Box::new(FeyAncestry)]
+    }
+}
+
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
+pub struct LightfootHalfling;
+
+identify!(LightfootHalfling, "5E::RACE::LIGHTFOOT_HALFLING");
+
+impl Race for LightfootHalfling {
+    fn ability_scores(&self) -> Vec<(&'static str, usize)> {
+        vec![(Dexterity.id(), 2), (Charisma.id(), 1)]
+    }
+
+    fn speed(&self) -> u32 {
+        25
+    }
+
+    fn size(&self) -> Size {
+        Size::Small
+    }
+
+    fn proficiencies(&self) -> Vec<Offer> {
+        vec![
+            Offer::new(Languages(Common)),
+            Offer::new(Languages(Halfling)),
+        ]
+    }
+
+    fn traits(&self) -> Vec<Box<dyn Feature>> {
+        vec![Box::new(HalflingLuck), Box::new(Brave)]
+    }
+}
+
+///
+/// +1 to every ability score.
+///
+/// The extra language of choice is left to the caller.
+///
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
+pub struct Human;
+
+identify!(Human, "5E::RACE::HUMAN");
+
+impl Race for Human {
+    fn ability_scores(&self) -> Vec<(&'static str, usize)> {
+        vec![
+            (Strength.id(), 1),
+            (Dexterity.id(), 1),
+            (Constitution.id(), 1),
+            (Intelligence.id(), 1),
+            (Wisdom.id(), 1),
+            (Charisma.id(), 1),
+        ]
+    }
+
+    fn proficiencies(&self) -> Vec<Offer> {
+        vec![Offer::new(Languages(Common))]
+    }
+}
@@ -0,0 +1,420 @@
+//!
+//! Spells, and the casting thereof.
+//!
+//! ### Examples
+//! ***
+//! A 5th level wizard, multiclassed into a 2nd level paladin:
+//! ```
+//! use xander::{
+//!     ability::{Charisma, Intelligence},
+//!     spell::slots::{Caster, Progression, Spellcasting},
+//! };
+//!
+//! let mut casting = Spellcasting::default();
+//! casting
+//!     .add(Caster::new("5E::CLASS::WIZARD", Intelligence, Progression::Full, 5))
+//!     .add(Caster::new("5E::CLASS::PALADIN", Charisma, Progression::Half, 2));
+//!
+//! assert_eq!(casting.caster_level(), 6);
+//! assert_eq!(casting.available(3), 3);
+//! ```
+//! ***
+//! Upcasting a preset spell:
+//! ```
+//! use xander::{dice::D6, spell::*};
+//!
+//! let damage = Fireball.damage(5, 9).unwrap(); // 5th level slot.
+//! assert_eq!(damage[D6].len(), 10);
+//! ```
+//!
+
+pub mod slots;
+
+use xander_macros::spells;
+
+use crate::{
+    ability::{Ability, Charisma, Constitution, Dexterity, Wisdom},
+    damage::DamageType,
+    dice::{Rolls, D10, D4, D6, D8},
+    identity::Identity,
+    item::weapon::DamageRoll,
+};
+
+///
+/// Schools of magic.
+///
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
+pub enum School {
+    Abjuration,
+    Conjuration,
+    Divination,
+    Enchantment,
+    Evocation,
+    Illusion,
+    Necromancy,
+    Transmutation,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Component {
+    Verbal,
+    Somatic,
+    ///
+    /// Material component, as described in the spell.
+    ///
+    Material(&'static str),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CastingTime {
+    Action,
+    BonusAction,
+    Reaction,
+    Minutes(u32),
+    Hours(u32),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Range {
+    ///
+    /// The caster only, or an area originating from them.
+    ///
+    Personal,
+    Touch,
+    Feet(u32),
+    Sight,
+    Unlimited,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Duration {
+    Instantaneous,
+    Rounds(u32),
+    Minutes(u32),
+    Hours(u32),
+    Days(u32),
+    UntilDispelled,
+}
+
+///
+/// What happens to a target that succeeds on its saving throw.
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OnSave {
+    Half,
+    Negates,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AttackKind {
+    Melee,
+    Ranged,
+}
+
+///
+/// How a spell gets to its targets.
+///
+#[derive(Debug, Clone, Copy)]
+pub enum Effect {
+    ///
+    /// Each target makes a saving throw.
+    ///
+    Save(&'static dyn Ability, OnSave),
+    ///
+    /// The caster makes a spell attack.
+    ///
+    Attack(AttackKind),
+    ///
+    /// No roll needed (e.g. Magic Missile, buffs).
+    ///
+    Automatic,
+}
+
+///
+/// How a spell's damage grows.
+///
+#[derive(Debug, Clone, Copy)]
+pub enum Scaling {
+    None,
+    ///
+    /// Extra damage for each slot level above the spell's own.
+    ///
+    PerSlotLevel(DamageRoll),
+    ///
+    /// Extra damage at character levels 5, 11 and 17 (cantrips).
+    ///
+    PerTier(DamageRoll),
+}
+
+///
+/// Cantrip damage tier, by character level.
+///
+pub fn tier(character_level: usize) -> usize {
+    match character_level {
+        0..=4 => 1,
+        5..=10 => 2,
+        11..=16 => 3,
+        _ => 4,
+    }
+}
+
+pub trait Spell: Identity {
+    ///
+    /// Spell level, `0` for cantrips.
+    ///
+    fn level(&self) -> usize;
+
+    fn school(&self) -> School;
+
+    fn casting_time(&self) -> CastingTime;
+
+    fn range(&self) -> Range;
+
+    fn components(&self) -> Vec<Component>;
+
+    fn duration(&self) -> Duration;
+
+    fn concentration(&self) -> bool;
+
+    fn ritual(&self) -> bool;
+
+    fn effect(&self) -> Effect;
+
+    fn damage_type(&self) -> Option<DamageType>;
+
+    ///
+    /// Roll the damage at the spell's own level.
+    ///
+    fn base_damage(&self) -> Option<Rolls>;
+
+    fn scaling(&self) -> Scaling;
+
+    fn cantrip(&self) -> bool {
+        self.level() == 0
+    }
+
+    ///
+    /// Roll damage, when cast using a `slot_level` slot
+    /// by a creature of `character_level`.
+    ///
+    /// A leveled spell can't be cast with a slot below its level.
+    ///
+    fn damage(&self, slot_level: usize, character_level: usize) -> Option<Rolls> {
+        if !self.cantrip() && slot_level < self.level() {
+            return None;
+        }
+
+        let base = self.base_damage()?;
+
+        Some(match self.scaling() {
+            Scaling::None => base,
+            Scaling::PerSlotLevel(extra) => {
+                (self.level()..slot_level).fold(base, |r, _| r + extra())
+            }
+            Scaling::PerTier(extra) => (1..tier(character_level)).fold(base, |r, _| r + extra()),
+        })
+    }
+}
+
+spells!(
+    Bane {
+        level: 1,
+        school: Enchantment,
+        casting_time: Action,
+        range: Feet(30),
+        components: [Verbal, Somatic, Material("a drop of blood")],
+        duration: Minutes(1),
+        concentration,
+        effect: Save(&Charisma, Negates),
+    },
+    Bless {
+        level: 1,
+        school: Enchantment,
+        casting_time: Action,
+        range: Feet(30),
+        components: [Verbal, Somatic, Material("a sprinkling of holy water")],
+        duration: Minutes(1),
+        concentration,
+        effect: Automatic,
+    },
+    BurningHands {
+        level: 1,
+        school: Evocation,
+        casting_time: Action,
+        range: Personal,
+        components: [Verbal, Somatic],
+        duration: Instantaneous,
+        effect: Save(&Dexterity, Half),
+        damage: (D6(3), Fire),
+        upcast: D6(1),
+    },
+    ConeOfCold {
+        level: 5,
+        school: Evocation,
+        casting_time: Action,
+        range: Personal,
+        components: [Verbal, Somatic, Material("a small crystal or glass cone")],
+        duration: Instantaneous,
+        effect: Save(&Constitution, Half),
+        damage: (D8(8), Cold),
+        upcast: D8(1),
+    },
+    ///
+    /// A bright streak flashes from your pointing finger,
+    /// then blossoms into an explosion of flame.
+    ///
+    Fireball {
+        level: 3,
+        school: Evocation,
+        casting_time: Action,
+        range: Feet(150),
+        components: [Verbal, Somatic, Material("a tiny ball of bat guano and sulfur")],
+        duration: Instantaneous,
+        effect: Save(&Dexterity, Half),
+        damage: (D6(8), Fire),
+        upcast: D6(1),
+    },
+    FireBolt {
+        level: 0,
+        school: Evocation,
+        casting_time: Action,
+        range: Feet(120),
+        components: [Verbal, Somatic],
+        duration: Instantaneous,
+        effect: Attack(Ranged),
+        damage: (D10(1), Fire),
+        cantrip: D10(1),
+    },
+    Guidance {
+        level: 0,
+        school: Divination,
+        casting_time: Action,
+        range: Touch,
+        components: [Verbal, Somatic],
+        duration: Minutes(1),
+        concentration,
+        effect: Automatic,
+    },
+    GuidingBolt {
+        level: 1,
+        school: Evocation,
+        casting_time: Action,
+        range: Feet(120),
+        components: [Verbal, Somatic],
+        duration: Rounds(1),
+        effect: Attack(Ranged),
+        damage: (D6(4), Radiant),
+        upcast: D6(1),
+    },
+    HoldPerson {
+        level: 2,
+        school: Enchantment,
+        casting_time: Action,
+        range: Feet(60),
+        components: [Verbal, Somatic, Material("a small, straight piece of iron")],
+        duration: Minutes(1),
+        concentration,
+        effect: Save(&Wisdom, Negates),
+    },
+    ///
+    /// Three darts, each dealing 1d4 + 1 force damage.
+    ///
+    MagicMissile {
+        level: 1,
+        school: Evocation,
+        casting_time: Action,
+        range: Feet(120),
+        components: [Verbal, Somatic],
+        duration: Instantaneous,
+        effect: Automatic,
+        damage: (D4(3) + 3, Force),
+        upcast: D4(1) + 1,
+    },
+    LightningBolt {
+        level: 3,
+        school: Evocation,
+        casting_time: Action,
+        range: Personal,
+        components: [Verbal, Somatic, Material("a bit of fur and a rod of amber, crystal, or glass")],
+        duration: Instantaneous,
+        effect: Save(&Dexterity, Half),
+        damage: (D6(8), Lightning),
+        upcast: D6(1),
+    },
+    SacredFlame {
+        level: 0,
+        school: Evocation,
+        casting_time: Action,
+        range: Feet(60),
+        components: [Verbal, Somatic],
+        duration: Instantaneous,
+        effect: Save(&Dexterity, Negates),
+        damage: (D8(1), Radiant),
+        cantrip: D8(1),
+    },
+    ShockingGrasp {
+        level: 0,
+        school: Evocation,
+        casting_time: Action,
+        range: Touch,
+        components: [Verbal, Somatic],
+        duration: Instantaneous,
+        effect: Attack(Melee),
+        damage: (D8(1), Lightning),
+        cantrip: D8(1),
+    },
+    DetectMagic {
+        level: 1,
+        school: Divination,
+        casting_time: Action,
+        range: Personal,
+        components: [Verbal, Somatic],
+        duration: Minutes(10),
+        concentration,
+        ritual,
+        effect: Automatic,
+    }
+);
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        damage::DamageType,
+        dice::{D10, D4, D6},
+        identity::Identity,
+    };
+
+    use super::{DetectMagic, Effect, FireBolt, Fireball, MagicMissile, OnSave, School, Spell};
+
+    #[test]
+    fn definitions() {
+        assert_eq!(Fireball.id(), "5E::SPELL::FIREBALL");
+        assert_eq!(Fireball.level(), 3);
+        assert_eq!(Fireball.school(), School::Evocation);
+        assert_eq!(Fireball.damage_type(), Some(DamageType::Fire));
+        assert!(matches!(Fireball.effect(), Effect::Save(_, OnSave::Half)));
+        assert!(!Fireball.concentration());
+
+        assert!(DetectMagic.ritual() && DetectMagic.concentration());
+        assert!(DetectMagic.damage(1, 1).is_none());
+    }
+
+    #[test]
+    fn upcasting() {
+        assert_eq!(Fireball.damage(3, 5).unwrap()[D6].len(), 8);
+        assert_eq!(Fireball.damage(6, 11).unwrap()[D6].len(), 11);
+        assert!(Fireball.damage(2, 5).is_none());
+
+        let missiles = MagicMissile.damage(2, 3).unwrap();
+        assert_eq!(missiles[D4].len(), 4);
+        assert!((8..=20).contains(&missiles.total()));
+    }
+
+    #[test]
+    fn cantrip_scaling() {
+        assert!(FireBolt.cantrip());
+        assert_eq!(FireBolt.damage(0, 4).unwrap()[D10].len(), 1);
+        assert_eq!(FireBolt.damage(0, 5).unwrap()[D10].len(), 2);
+        assert_eq!(FireBolt.damage(0, 17).unwrap()[D10].len(), 4);
+    }
+}
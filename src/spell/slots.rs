@@ -0,0 +1,460 @@
+use crate::ability::Ability;
+
+///
+/// Highest level of spell slot.
+///
+pub const MAX_SLOT_LEVEL: usize = 9;
+
+///
+/// Spell slots per slot level, for a full caster of each level.
+///
+const FULL_CASTER: [[usize; MAX_SLOT_LEVEL]; 20] = [
+    [2, 0, 0, 0, 0, 0, 0, 0, 0],
+    [3, 0, 0, 0, 0, 0, 0, 0, 0],
+    [4, 2, 0, 0, 0, 0, 0, 0, 0],
+    [4, 3, 0, 0, 0, 0, 0, 0, 0],
+    [4, 3, 2, 0, 0, 0, 0, 0, 0],
+    [4, 3, 3, 0, 0, 0, 0, 0, 0],
+    [4, 3, 3, 1, 0, 0, 0, 0, 0],
+    [4, 3, 3, 2, 0, 0, 0, 0, 0],
+    [4, 3, 3, 3, 1, 0, 0, 0, 0],
+    [4, 3, 3, 3, 2, 0, 0, 0, 0],
+    [4, 3, 3, 3, 2, 1, 0, 0, 0],
+    [4, 3, 3, 3, 2, 1, 0, 0, 0],
+    [4, 3, 3, 3, 2, 1, 1, 0, 0],
+    [4, 3, 3, 3, 2, 1, 1, 0, 0],
+    [4, 3, 3, 3, 2, 1, 1, 1, 0],
+    [4, 3, 3, 3, 2, 1, 1, 1, 0],
+    [4, 3, 3, 3, 2, 1, 1, 1, 1],
+    [4, 3, 3, 3, 3, 1, 1, 1, 1],
+    [4, 3, 3, 3, 3, 2, 1, 1, 1],
+    [4, 3, 3, 3, 3, 2, 2, 1, 1],
+];
+
+///
+/// `(slots, slot level)` of Pact Magic, per warlock level.
+///
+const PACT_MAGIC: [(usize, usize); 20] = [
+    (1, 1),
+    (2, 1),
+    (2, 2),
+    (2, 2),
+    (2, 3),
+    (2, 3),
+    (2, 4),
+    (2, 4),
+    (2, 5),
+    (2, 5),
+    (3, 5),
+    (3, 5),
+    (3, 5),
+    (3, 5),
+    (3, 5),
+    (3, 5),
+    (4, 5),
+    (4, 5),
+    (4, 5),
+    (4, 5),
+];
+
+///
+/// How quickly a class gains spell slots.
+///
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
+pub enum Progression {
+    ///
+    /// Bard, Cleric, Druid, Sorcerer, Wizard.
+    ///
+    Full,
+    ///
+    /// Paladin, Ranger.
+    ///
+    Half,
+    ///
+    /// Eldritch Knight, Arcane Trickster.
+    ///
+    Third,
+    ///
+    /// Warlock: separate slots, recovered on a short rest.
+    ///
+    Pact,
+}
+
+///
+/// A class's spellcasting feature.
+///
+#[derive(Debug)]
+pub struct Caster {
+    class: &'static str,
+    ability: Box<dyn Ability>,
+    progression: Progression,
+    level: usize,
+}
+
+impl Caster {
+    pub fn new(
+        class: &'static str,
+        ability: impl Ability + 'static,
+        progression: Progression,
+        level: usize,
+    ) -> Self {
+        Self {
+            class,
+            ability: Box::new(ability),
+            progression,
+            level,
+        }
+    }
+
+    ///
+    /// Id of the class this feature belongs to.
+    ///
+    pub fn class(&self) -> &'static str {
+        self.class
+    }
+
+    ///
+    /// Spellcasting ability of this class.
+    ///
+    pub fn ability(&self) -> &dyn Ability {
+        self.ability.as_ref()
+    }
+
+    pub fn progression(&self) -> Progression {
+        self.progression
+    }
+
+    ///
+    /// Levels taken in this class.
+    ///
+    pub fn level(&self) -> usize {
+        self.level
+    }
+
+    ///
+    /// Caster level of this class on its own.
+    ///
+    fn own_level(&self) -> usize {
+        match self.progression {
+            Progression::Full => self.level,
+            Progression::Half if self.level >= 2 => self.level.div_ceil(2),
+            Progression::Third if self.level >= 3 => self.level.div_ceil(3),
+            _ => 0,
+        }
+    }
+
+    ///
+    /// Highest level of spell this class can learn,
+    /// going by its own levels only.
+    ///
+    pub fn spell_level(&self) -> usize {
+        match (self.progression, self.level.min(20)) {
+            (_, 0) => 0,
+            (Progression::Pact, level) => PACT_MAGIC[level - 1].1,
+            _ => match self.own_level().min(20) {
+                0 => 0,
+                level => FULL_CASTER[level - 1]
+                    .iter()
+                    .rposition(|slots| *slots > 0)
+                    .map_or(0, |i| i + 1),
+            },
+        }
+    }
+}
+
+///
+/// Errors when spending spell slots.
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SlotError {
+    ///
+    /// Slot levels go from 1 to [MAX_SLOT_LEVEL].
+    ///
+    InvalidLevel(usize),
+    ///
+    /// No slots of this level left.
+    ///
+    Exhausted(usize),
+    ///
+    /// Not a Pact Magic caster.
+    ///
+    NoPactMagic,
+}
+
+///
+/// Every spellcasting feature of a creature,
+/// and the slots they share.
+///
+#[derive(Debug, Default)]
+pub struct Spellcasting {
+    casters: Vec<Caster>,
+    expended: [usize; MAX_SLOT_LEVEL],
+    pact_expended: usize,
+}
+
+impl Spellcasting {
+    ///
+    /// Add (or replace) the spellcasting feature of a class.
+    ///
+    pub fn add(&mut self, caster: Caster) -> &mut Self {
+        self.casters.retain(|c| c.class != caster.class);
+        self.casters.push(caster);
+        self
+    }
+
+    ///
+    /// Remove the spellcasting feature of a class.
+    ///
+    pub fn remove(&mut self, class: &str) -> &mut Self {
+        self.casters.retain(|c| c.class != class);
+        self
+    }
+
+    pub fn caster(&self, class: &str) -> Option<&Caster> {
+        self.casters.iter().find(|c| c.class == class)
+    }
+
+    pub fn casters(&self) -> impl Iterator<Item = &Caster> {
+        self.casters.iter()
+    }
+
+    ///
+    /// Effective caster level, for the (shared) spell slot table.
+    ///
+    /// A single class uses its own table (half casters from 2nd level,
+    /// third casters from 3rd); multiclassing adds up full levels,
+    /// half of half caster levels and a third of third caster levels,
+    /// rounded down. Pact Magic never counts.
+    ///
+    pub fn caster_level(&self) -> usize {
+        let casters = self
+            .casters
+            .iter()
+            .filter(|c| c.progression != Progression::Pact)
+            .collect::<Vec<_>>();
+
+        match casters.as_slice() {
+            [single] => single.own_level(),
+            casters => casters
+                .iter()
+                .map(|c| match c.progression {
+                    Progression::Full => c.level,
+                    Progression::Half => c.level / 2,
+                    Progression::Third => c.level / 3,
+                    Progression::Pact => 0,
+                })
+                .sum(),
+        }
+    }
+
+    ///
+    /// Total slots of a level, expended or not.
+    ///
+    pub fn slots(&self, level: usize) -> usize {
+        match (self.caster_level(), level) {
+            (0, _) | (_, 0) => 0,
+            (_, level) if level > MAX_SLOT_LEVEL => 0,
+            (caster, level) => FULL_CASTER[caster.min(20) - 1][level - 1],
+        }
+    }
+
+    ///
+    /// Slots of a level left to spend.
+    ///
+    pub fn available(&self, level: usize) -> usize {
+        match level {
+            1..=MAX_SLOT_LEVEL => self.slots(level).saturating_sub(self.expended[level - 1]),
+            _ => 0,
+        }
+    }
+
+    ///
+    /// Spend a spell slot of the given level.
+    ///
+    pub fn expend(&mut self, level: usize) -> Result<(), SlotError> {
+        if !(1..=MAX_SLOT_LEVEL).contains(&level) {
+            return Err(SlotError::InvalidLevel(level));
+        }
+
+        match self.available(level) {
+            0 => Err(SlotError::Exhausted(level)),
+            _ => {
+                self.expended[level - 1] += 1;
+                Ok(())
+            }
+        }
+    }
+
+    ///
+    /// Regain a single expended slot (e.g. Arcane Recovery).
+    ///
+    pub fn restore(&mut self, level: usize) -> &mut Self {
+        if (1..=MAX_SLOT_LEVEL).contains(&level) {
+            self.expended[level - 1] = self.expended[level - 1].saturating_sub(1);
+        }
+        self
+    }
+
+    ///
+    /// `(slots, slot level)` of Pact Magic, if any.
+    ///
+    pub fn pact_slots(&self) -> Option<(usize, usize)> {
+        self.casters
+            .iter()
+            .find(|c| c.progression == Progression::Pact && c.level > 0)
+            .map(|c| PACT_MAGIC[c.level.min(20) - 1])
+    }
+
+    ///
+    /// Pact Magic slots left to spend.
+    ///
+    pub fn pact_available(&self) -> usize {
+        self.pact_slots()
+            .map(|(slots, _)| slots.saturating_sub(self.pact_expended))
+            .unwrap_or(0)
+    }
+
+    ///
+    /// Spend a Pact Magic slot,
+    /// returning the level it is cast at.
+    ///
+    pub fn expend_pact(&mut self) -> Result<usize, SlotError> {
+        let (_, level) = self.pact_slots().ok_or(SlotError::NoPactMagic)?;
+
+        match self.pact_available() {
+            0 => Err(SlotError::Exhausted(level)),
+            _ => {
+                self.pact_expended += 1;
+                Ok(level)
+            }
+        }
+    }
+
+    ///
+    /// Pact Magic slots come back on a short rest.
+    ///
+    pub fn short_rest(&mut self) -> &mut Self {
+        self.pact_expended = 0;
+        self
+    }
+
+    ///
+    /// Every slot comes back on a long rest.
+    ///
+    pub fn long_rest(&mut self) -> &mut Self {
+        self.expended = [0; MAX_SLOT_LEVEL];
+        self.pact_expended = 0;
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ability::{Charisma, Intelligence, Wisdom};
+
+    use super::{Caster, Progression, SlotError, Spellcasting};
+
+    #[test]
+    fn single_class() {
+        let mut casting = Spellcasting::default();
+        casting.add(Caster::new(
+            "5E::CLASS::PALADIN",
+            Charisma,
+            Progression::Half,
+            1,
+        ));
+        assert_eq!(casting.slots(1), 0);
+
+        casting.add(Caster::new(
+            "5E::CLASS::PALADIN",
+            Charisma,
+            Progression::Half,
+            5,
+        ));
+        assert_eq!((casting.slots(1), casting.slots(2)), (4, 2));
+
+        let mut knight = Spellcasting::default();
+        knight.add(Caster::new(
+            "5E::SUBCLASS::ELDRITCH_KNIGHT",
+            Intelligence,
+            Progression::Third,
+            19,
+        ));
+        assert_eq!(knight.caster_level(), 7);
+        assert_eq!(knight.slots(4), 1);
+    }
+
+    #[test]
+    fn multiclass() {
+        let mut casting = Spellcasting::default();
+        casting
+            .add(Caster::new(
+                "5E::CLASS::CLERIC",
+                Wisdom,
+                Progression::Full,
+                3,
+            ))
+            .add(Caster::new(
+                "5E::CLASS::RANGER",
+                Wisdom,
+                Progression::Half,
+                3,
+            ))
+            .add(Caster::new(
+                "5E::CLASS::WARLOCK",
+                Charisma,
+                Progression::Pact,
+                3,
+            ));
+
+        assert_eq!(casting.caster_level(), 4);
+        assert_eq!(
+            (casting.slots(1), casting.slots(2), casting.slots(3)),
+            (4, 3, 0)
+        );
+        assert_eq!(casting.pact_slots(), Some((2, 2)));
+
+        // Spells learned go by each class's own levels.
+        let levels = casting
+            .casters()
+            .map(Caster::spell_level)
+            .collect::<Vec<_>>();
+        assert_eq!(levels, [2, 1, 2]);
+
+        casting.remove("5E::CLASS::CLERIC");
+        assert_eq!(casting.caster_level(), 2);
+    }
+
+    #[test]
+    fn expend_and_rest() {
+        let mut casting = Spellcasting::default();
+        casting
+            .add(Caster::new(
+                "5E::CLASS::WIZARD",
+                Intelligence,
+                Progression::Full,
+                1,
+            ))
+            .add(Caster::new(
+                "5E::CLASS::WARLOCK",
+                Charisma,
+                Progression::Pact,
+                1,
+            ));
+
+        assert_eq!(casting.expend(1), Ok(()));
+        assert_eq!(casting.expend(1), Ok(()));
+        assert_eq!(casting.expend(1), Err(SlotError::Exhausted(1)));
+        assert_eq!(casting.expend(10), Err(SlotError::InvalidLevel(10)));
+
+        assert_eq!(casting.expend_pact(), Ok(1));
+        assert_eq!(casting.pact_available(), 0);
+
+        casting.short_rest();
+        assert_eq!(casting.pact_available(), 1);
+        assert_eq!(casting.available(1), 0);
+
+        casting.long_rest();
+        assert_eq!(casting.available(1), 2);
+    }
+}